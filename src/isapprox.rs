@@ -5,14 +5,15 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use core::fmt::{Debug, Display};
 use num_traits::Float;
-use std::fmt::{Debug, Display};
 
 const ATOL_DEFAULT: f64 = 1e-8;
 const ATOL_DEFAULT_ERR_MSG: &str = "Failed to create atol from default value";
 const RTOL_DEFAULT_ERR_MSG: &str = "Failed to create rtol from epsilon";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NanComparison {
     #[default]
     Equal,
@@ -34,6 +35,36 @@ impl From<NanComparison> for bool {
     }
 }
 
+// How `atol` and `rtol` combine into the largest difference allowed between `x` and `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToleranceRule {
+    // max(atol, rtol * max(|x|, |y|))
+    #[default]
+    Max,
+    // max(atol, rtol * min(|x|, |y|))
+    Min,
+    // atol + rtol * max(|x|, |y|)
+    Sum,
+    // atol + rtol * |y|, as in NumPy's `isclose(x, y)`, so only `y` serves as the reference
+    NumPy,
+}
+
+impl ToleranceRule {
+    #[inline]
+    pub(crate) fn combine<F>(self, atol: F, rtol: F, mag_x: F, mag_y: F) -> F
+    where
+        F: Float,
+    {
+        match self {
+            ToleranceRule::Max => atol.max(rtol * mag_x.max(mag_y)),
+            ToleranceRule::Min => atol.max(rtol * mag_x.min(mag_y)),
+            ToleranceRule::Sum => atol + rtol * mag_x.max(mag_y),
+            ToleranceRule::NumPy => atol + rtol * mag_y,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum TolsError<F>
 where
@@ -41,13 +72,15 @@ where
 {
     NegativeAtol(F),
     NegativeRtol(F),
+    NonFiniteAtol(F),
+    NonFiniteRtol(F),
 }
 
 impl<F> Display for TolsError<F>
 where
     F: Float + Display + Debug,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
             TolsError::NegativeAtol(value) => {
                 write!(f, "atol must be non-negative, got {}", value)
@@ -55,19 +88,32 @@ where
             TolsError::NegativeRtol(value) => {
                 write!(f, "rtol must be non-negative, got {}", value)
             }
+            TolsError::NonFiniteAtol(value) => {
+                write!(f, "atol must be finite, got {}", value)
+            }
+            TolsError::NonFiniteRtol(value) => {
+                write!(f, "rtol must be finite, got {}", value)
+            }
         }
     }
 }
 
-impl<F> std::error::Error for TolsError<F> where F: Float + Display + Debug {}
+impl<F> core::error::Error for TolsError<F> where F: Float + Display + Debug {}
 
+// Built with `Tols::new` and the `with_*` methods, so that new fields can be added without
+// breaking callers
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "TolsUnchecked<F>"))]
+#[non_exhaustive]
 pub struct Tols<F>
 where
     F: Float + Display + Debug,
 {
     pub atol: F,
     pub rtol: F,
+    pub max_ulps: u64,
+    pub rule: ToleranceRule,
 }
 
 impl<F> Tols<F>
@@ -75,16 +121,83 @@ where
     F: Float + Display + Debug,
 {
     pub fn new(atol: F, rtol: F) -> Result<Self, TolsError<F>> {
-        if atol.is_sign_negative() {
-            Err(TolsError::NegativeAtol(atol))
-        } else if rtol.is_sign_negative() {
-            Err(TolsError::NegativeRtol(rtol))
+        let tols = Tols {
+            atol,
+            rtol,
+            max_ulps: 0,
+            rule: ToleranceRule::default(),
+        };
+        tols.validate().map(|_| tols)
+    }
+
+    // The fields are public, so tolerances built without `Tols::new` are checked here instead
+    pub fn validate(&self) -> Result<(), TolsError<F>> {
+        if !self.atol.is_finite() {
+            Err(TolsError::NonFiniteAtol(self.atol))
+        } else if !self.rtol.is_finite() {
+            Err(TolsError::NonFiniteRtol(self.rtol))
+        } else if self.atol.is_sign_negative() {
+            Err(TolsError::NegativeAtol(self.atol))
+        } else if self.rtol.is_sign_negative() {
+            Err(TolsError::NegativeRtol(self.rtol))
         } else {
-            Ok(Tols { atol, rtol })
+            Ok(())
+        }
+    }
+
+    #[inline]
+    pub fn with_max_ulps(self, max_ulps: u64) -> Self {
+        Tols { max_ulps, ..self }
+    }
+
+    #[inline]
+    pub fn with_rule(self, rule: ToleranceRule) -> Self {
+        Tols { rule, ..self }
+    }
+
+    // Largest difference allowed between two values with magnitudes `mag_x` and `mag_y`
+    #[inline]
+    pub fn tol(&self, mag_x: F, mag_y: F) -> F {
+        self.rule.combine(self.atol, self.rtol, mag_x, mag_y)
+    }
+
+    // Pins the magnitudes that `rtol` is relative to at `scale`, as MATLAB's `DataScale` does,
+    // which leaves a purely absolute tolerance
+    #[inline]
+    pub fn scaled(self, scale: F) -> Self {
+        Tols {
+            atol: self.tol(scale, scale),
+            rtol: F::zero(),
+            ..self
         }
     }
 }
 
+// Deserialized tolerances are routed through `Tols::new` so that invalid values are rejected
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TolsUnchecked<F> {
+    atol: F,
+    rtol: F,
+    #[serde(default)]
+    max_ulps: u64,
+    #[serde(default)]
+    rule: ToleranceRule,
+}
+
+#[cfg(feature = "serde")]
+impl<F> TryFrom<TolsUnchecked<F>> for Tols<F>
+where
+    F: Float + Display + Debug,
+{
+    type Error = TolsError<F>;
+
+    fn try_from(value: TolsUnchecked<F>) -> Result<Self, Self::Error> {
+        Tols::new(value.atol, value.rtol)
+            .map(|tols| tols.with_max_ulps(value.max_ulps).with_rule(value.rule))
+    }
+}
+
 impl<F> Default for Tols<F>
 where
     F: Float + Display + Debug,
@@ -93,10 +206,55 @@ where
         Self {
             atol: F::from(ATOL_DEFAULT).expect(ATOL_DEFAULT_ERR_MSG),
             rtol: F::from(F::epsilon()).expect(RTOL_DEFAULT_ERR_MSG).sqrt(),
+            max_ulps: 0,
+            rule: ToleranceRule::default(),
         }
     }
 }
 
+pub trait ApproxEq<F> {
+    fn isapprox(&self, x: F, y: F, nan_cmp: NanComparison) -> bool;
+
+    // Whether a value within tolerance of the farthest member of a sorted run is within tolerance
+    // of every member, which lets complete linkage skip the pairwise check
+    #[inline]
+    fn is_monotone(&self) -> bool {
+        false
+    }
+}
+
+impl<F, T> ApproxEq<F> for &T
+where
+    T: ApproxEq<F> + ?Sized,
+{
+    #[inline]
+    fn isapprox(&self, x: F, y: F, nan_cmp: NanComparison) -> bool {
+        (**self).isapprox(x, y, nan_cmp)
+    }
+
+    #[inline]
+    fn is_monotone(&self) -> bool {
+        (**self).is_monotone()
+    }
+}
+
+impl<F> ApproxEq<F> for Tols<F>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn isapprox(&self, x: F, y: F, nan_cmp: NanComparison) -> bool {
+        isapprox(x, y, *self, nan_cmp)
+    }
+
+    // Every rule's tolerance moves by at most `rtol` times the change in a magnitude, so with
+    // `rtol <= 1` it never shrinks faster than the gap as a member nears the new value
+    #[inline]
+    fn is_monotone(&self) -> bool {
+        self.rtol <= F::one()
+    }
+}
+
 #[inline]
 pub fn isapprox<F>(x: F, y: F, tols: Tols<F>, nan_cmp: NanComparison) -> bool
 where
@@ -114,8 +272,208 @@ where
         return true;
     }
 
-    let Tols { atol, rtol } = tols;
-    let max_val = x.abs().max(y.abs());
-    let tol = atol.max(rtol * max_val);
-    (x - y).abs() <= tol
+    (x - y).abs() <= tols.tol(x.abs(), y.abs()) || isapprox_ulps(x, y, tols.max_ulps)
+}
+
+// Position of `x` in the sequence of representable values, with `+0` and `-0` both at zero.
+// The mantissa width and exponent offset are recovered from `epsilon` and
+// `min_positive_value`, so this works for any IEEE 754 binary format (`f32`, `f64`, ...)
+fn ulps_ordinal<F>(x: F) -> i128
+where
+    F: Float,
+{
+    let (mantissa_eps, _, _) = F::epsilon().integer_decode();
+    let (_, exponent_min, _) = F::min_positive_value().integer_decode();
+    let (mantissa, exponent, sign) = x.integer_decode();
+
+    let mantissa_bits = mantissa_eps.trailing_zeros();
+    let implicit_bit = 1u64 << mantissa_bits;
+
+    let magnitude = if exponent < exponent_min {
+        // Subnormals (and zero) are decoded with their mantissa shifted left by one
+        i128::from(mantissa >> 1)
+    } else {
+        let exponent_biased = i128::from(exponent - exponent_min + 1);
+        (exponent_biased << mantissa_bits) + i128::from(mantissa - implicit_bit)
+    };
+
+    i128::from(sign) * magnitude
+}
+
+#[inline]
+pub fn isapprox_ulps<F>(x: F, y: F, max_ulps: u64) -> bool
+where
+    F: Float,
+{
+    max_ulps > 0
+        && x.is_finite()
+        && y.is_finite()
+        && (ulps_ordinal(x) - ulps_ordinal(y)).unsigned_abs() <= u128::from(max_ulps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tols_ulps<F>(max_ulps: u64) -> Tols<F>
+    where
+        F: Float + Display + Debug,
+    {
+        Tols::new(F::zero(), F::zero())
+            .unwrap()
+            .with_max_ulps(max_ulps)
+    }
+
+    #[test]
+    fn test_isapprox_ulps_f64() {
+        let nan_cmp = NanComparison::default();
+        let x = 1.0_f64;
+        let y = f64::from_bits(x.to_bits() + 3);
+        let z = f64::from_bits(x.to_bits() - 2);
+
+        assert!(isapprox(x, y, tols_ulps(3), nan_cmp));
+        assert!(!isapprox(x, y, tols_ulps(2), nan_cmp));
+        assert!(isapprox(z, y, tols_ulps(5), nan_cmp));
+        assert!(!isapprox(z, y, tols_ulps(4), nan_cmp));
+        assert!(!isapprox(x, y, Tols::new(0.0, 0.0).unwrap(), nan_cmp));
+    }
+
+    #[test]
+    fn test_isapprox_ulps_f32() {
+        let nan_cmp = NanComparison::default();
+        let x = 1.0_f32;
+        let y = f32::from_bits(x.to_bits() + 3);
+
+        assert!(isapprox(x, y, tols_ulps(3), nan_cmp));
+        assert!(!isapprox(x, y, tols_ulps(2), nan_cmp));
+        assert!(isapprox(
+            f32::MAX,
+            f32::from_bits(f32::MAX.to_bits() - 1),
+            tols_ulps(1),
+            nan_cmp
+        ));
+    }
+
+    #[test]
+    fn test_isapprox_ulps_across_zero() {
+        let nan_cmp = NanComparison::default();
+        let tiny = f64::from_bits(1);
+
+        assert!(isapprox(0.0, -0.0, tols_ulps(0), nan_cmp));
+        assert!(isapprox(-tiny, tiny, tols_ulps(2), nan_cmp));
+        assert!(!isapprox(-tiny, tiny, tols_ulps(1), nan_cmp));
+        assert!(isapprox(-0.0, tiny, tols_ulps(1), nan_cmp));
+        assert!(isapprox(
+            f64::MIN_POSITIVE,
+            f64::from_bits(f64::MIN_POSITIVE.to_bits() - 1),
+            tols_ulps(1),
+            nan_cmp
+        ));
+    }
+
+    #[test]
+    fn test_isapprox_ulps_non_finite() {
+        let nan_cmp = NanComparison::default();
+        let tols = tols_ulps(u64::MAX);
+
+        assert!(isapprox(f64::INFINITY, f64::INFINITY, tols, nan_cmp));
+        assert!(!isapprox(f64::INFINITY, f64::MAX, tols, nan_cmp));
+        assert!(!isapprox(f64::NAN, 1.0, tols, nan_cmp));
+    }
+
+    #[test]
+    fn test_isapprox_ulps_combined() {
+        let nan_cmp = NanComparison::default();
+        let tols = Tols::new(1e-10, 0.0).unwrap().with_max_ulps(4);
+
+        assert!(isapprox(1e-11, 0.0, tols, nan_cmp));
+        assert!(isapprox(
+            1e10,
+            1e10 + 2.0 * f64::EPSILON * 1e10,
+            tols,
+            nan_cmp
+        ));
+        assert!(!isapprox(1e10, 1e10 + 1e-3, tols, nan_cmp));
+    }
+
+    #[test]
+    fn test_tols_validate() {
+        assert!(Tols::new(0.0, 0.0).is_ok());
+        assert!(matches!(
+            Tols::new(-1.0, 0.0),
+            Err(TolsError::NegativeAtol(_))
+        ));
+        assert!(matches!(
+            Tols::new(0.0, -0.0),
+            Err(TolsError::NegativeRtol(_))
+        ));
+        assert!(matches!(
+            Tols::new(f64::NAN, 0.0),
+            Err(TolsError::NonFiniteAtol(_))
+        ));
+        assert!(matches!(
+            Tols::new(0.0, f32::INFINITY),
+            Err(TolsError::NonFiniteRtol(_))
+        ));
+
+        let tols = Tols {
+            atol: -f64::INFINITY,
+            rtol: 1e-6,
+            max_ulps: 0,
+            rule: ToleranceRule::default(),
+        };
+        assert!(matches!(tols.validate(), Err(TolsError::NonFiniteAtol(_))));
+    }
+
+    #[test]
+    fn test_tolerance_rule() {
+        let nan_cmp = NanComparison::default();
+        let tols = Tols::new(0.5, 0.25).unwrap();
+        let isapprox_rule = |x: f64, y: f64, rule| isapprox(x, y, tols.with_rule(rule), nan_cmp);
+
+        assert_eq!(tols.tol(3.0, 4.5), 1.125);
+        assert_eq!(tols.with_rule(ToleranceRule::Min).tol(3.0, 4.5), 0.75);
+        assert_eq!(tols.with_rule(ToleranceRule::Sum).tol(3.0, 4.5), 1.625);
+        assert_eq!(tols.with_rule(ToleranceRule::NumPy).tol(4.5, 3.0), 1.25);
+
+        assert!(!isapprox_rule(3.0, 4.5, ToleranceRule::Max));
+        assert!(!isapprox_rule(3.0, 4.5, ToleranceRule::Min));
+        assert!(isapprox_rule(3.0, 4.5, ToleranceRule::Sum));
+        assert!(isapprox_rule(3.0, 4.5, ToleranceRule::NumPy));
+        assert!(!isapprox_rule(4.5, 3.0, ToleranceRule::NumPy));
+
+        assert!(isapprox_rule(3.0, 3.6, ToleranceRule::Min));
+        assert!(isapprox_rule(0.0, 0.5, ToleranceRule::Min));
+        assert!(!isapprox_rule(0.0, 0.6, ToleranceRule::Min));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tols_serde() {
+        let tols = Tols::new(1e-6, 1e-9)
+            .unwrap()
+            .with_max_ulps(4)
+            .with_rule(ToleranceRule::NumPy);
+        let json = serde_json::to_string(&tols).unwrap();
+        assert_eq!(serde_json::from_str::<Tols<f64>>(&json).unwrap(), tols);
+
+        let tols: Tols<f64> = serde_json::from_str(r#"{"atol": 0.5, "rtol": 0.0}"#).unwrap();
+        assert_eq!(tols, Tols::new(0.5, 0.0).unwrap());
+
+        let err = serde_json::from_str::<Tols<f64>>(r#"{"atol": -0.5, "rtol": 0.0}"#);
+        assert!(
+            err.unwrap_err()
+                .to_string()
+                .contains("atol must be non-negative")
+        );
+        let err = serde_json::from_str::<Tols<f64>>(r#"{"atol": 0.5, "rtol": -1.0}"#);
+        assert!(
+            err.unwrap_err()
+                .to_string()
+                .contains("rtol must be non-negative")
+        );
+
+        let nan_cmp: NanComparison = serde_json::from_str(r#""NotEqual""#).unwrap();
+        assert_eq!(nan_cmp, NanComparison::NotEqual);
+    }
 }
@@ -0,0 +1,236 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use ndarray::{
+    Array, ArrayView, AsArray, DimMax, Dimension, ErrorKind, IntoDimension, ShapeError, Zip,
+};
+use num_traits::Float;
+
+use crate::isapprox::{NanComparison, Tols, isapprox};
+use crate::uniquetol_error::UniqueTolError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseViolation<F> {
+    pub index: Vec<usize>,
+    pub x: F,
+    pub y: F,
+    // How many times over the tolerance `|x - y|` is, which is infinite whenever the pair fails
+    // over a NaN or an infinity
+    pub ratio: F,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllCloseResult<F> {
+    pub first: Option<CloseViolation<F>>,
+    pub worst: Option<CloseViolation<F>>,
+    pub count_violations: usize,
+}
+
+impl<F> AllCloseResult<F> {
+    #[inline]
+    pub fn is_all_close(&self) -> bool {
+        self.count_violations == 0
+    }
+}
+
+// Only meaningful for pairs that `isapprox` has already rejected
+fn violation_ratio<F>(x: F, y: F, tols: Tols<F>) -> F
+where
+    F: Float + Display + Debug,
+{
+    let diff = (x - y).abs();
+
+    if !diff.is_finite() {
+        return F::infinity();
+    }
+
+    diff / tols.tol(x.abs(), y.abs())
+}
+
+// Aligns the shapes at their trailing axes, as NumPy does, where each pair of lengths must
+// either agree or include a 1
+fn broadcast_dim<D, E>(
+    shape_a: &[usize],
+    shape_b: &[usize],
+) -> Result<<D as DimMax<E>>::Output, ShapeError>
+where
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+{
+    let ndim = shape_a.len().max(shape_b.len());
+    let len_at = |shape: &[usize], i: usize| {
+        (i + shape.len())
+            .checked_sub(ndim)
+            .map_or(1, |axis| shape[axis])
+    };
+
+    let mut dim = <D as DimMax<E>>::Output::zeros(ndim);
+
+    for (i, len) in dim.slice_mut().iter_mut().enumerate() {
+        *len = match (len_at(shape_a, i), len_at(shape_b, i)) {
+            (len_a, len_b) if len_a == len_b || len_b == 1 => len_a,
+            (1, len_b) => len_b,
+            _ => return Err(ShapeError::from_kind(ErrorKind::IncompatibleShape)),
+        };
+    }
+
+    Ok(dim)
+}
+
+// Broadcasts `a` and `b` against each other as NumPy would before comparing them elementwise
+pub fn isclose<'a, 'b, F, V, W, D, E>(
+    a: V,
+    b: W,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+) -> Result<Array<bool, <D as DimMax<E>>::Output>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug + 'a + 'b,
+    V: AsArray<'a, F, D>,
+    W: AsArray<'b, F, E>,
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+{
+    tols.validate()?;
+
+    let (a, b): (ArrayView<F, D>, ArrayView<F, E>) = (a.into(), b.into());
+    let dim = broadcast_dim::<D, E>(a.shape(), b.shape())?;
+    // Safe to unwrap: `dim` is compatible with both shapes by construction
+    let (a, b) = (a.broadcast(dim.clone()).unwrap(), b.broadcast(dim).unwrap());
+
+    Ok(Zip::from(&a)
+        .and(&b)
+        .map_collect(|&x, &y| isapprox(x, y, tols, nan_cmp)))
+}
+
+pub fn allclose<'a, 'b, F, V, W, D, E>(
+    a: V,
+    b: W,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+) -> Result<AllCloseResult<F>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug + 'a + 'b,
+    V: AsArray<'a, F, D>,
+    W: AsArray<'b, F, E>,
+    D: Dimension + DimMax<E>,
+    E: Dimension,
+{
+    tols.validate()?;
+
+    let (a, b): (ArrayView<F, D>, ArrayView<F, E>) = (a.into(), b.into());
+    let dim = broadcast_dim::<D, E>(a.shape(), b.shape())?;
+    // Safe to unwrap: `dim` is compatible with both shapes by construction
+    let (a, b) = (a.broadcast(dim.clone()).unwrap(), b.broadcast(dim).unwrap());
+
+    let mut result = AllCloseResult {
+        first: None,
+        worst: None,
+        count_violations: 0,
+    };
+
+    // Both views share one shape, so they are traversed in the same logical order
+    for ((idx, &x), &y) in a.indexed_iter().zip(b.iter()) {
+        if isapprox(x, y, tols, nan_cmp) {
+            continue;
+        }
+
+        let violation = CloseViolation {
+            index: idx.into_dimension().slice().to_vec(),
+            x,
+            y,
+            ratio: violation_ratio(x, y, tols),
+        };
+        result.count_violations += 1;
+
+        if result
+            .worst
+            .as_ref()
+            .is_none_or(|worst| violation.ratio > worst.ratio)
+        {
+            result.worst = Some(violation.clone());
+        }
+
+        if result.first.is_none() {
+            result.first = Some(violation);
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use ndarray::{array, aview1};
+
+    #[test]
+    fn test_isclose() {
+        let tols = Tols::new(1e-3, 0.0).unwrap();
+        let a = array![[1.0, 2.0, f64::NAN], [1.1, 2.0005, 3.0]];
+        let b = array![1.0, 2.0, f64::NAN];
+
+        let result = isclose(&a, &b, tols, NanComparison::default()).unwrap();
+        assert_eq!(result, array![[true, true, true], [false, true, false]]);
+
+        let result = isclose(&a, &b, tols, NanComparison::NotEqual).unwrap();
+        assert_eq!(result, array![[true, true, false], [false, true, false]]);
+
+        let x = [1.0, 2.0, 3.0];
+        let y = vec![1.0005, 2.1, 3.0];
+        let result = isclose(&x[..], &y, tols, NanComparison::default()).unwrap();
+        assert_eq!(result, array![true, false, true]);
+
+        let result = isclose(&a, &aview1(&[1.0, 2.0]), tols, NanComparison::default());
+        assert!(matches!(result, Err(UniqueTolError::Shape(_))));
+
+        let tols_bad = Tols { atol: -1.0, ..tols };
+        let result = isclose(&x[..], &y, tols_bad, NanComparison::default());
+        assert!(matches!(result, Err(UniqueTolError::Tols(_))));
+    }
+
+    #[test]
+    fn test_allclose() {
+        let tols = Tols::new(1e-3, 1e-2).unwrap();
+        let a = array![[1.0, 200.0], [3.0, f64::NAN]];
+        let b = array![[1.0, 203.0], [3.5, f64::NAN]];
+
+        let result = allclose(&a, &b, tols, NanComparison::default()).unwrap();
+        assert!(!result.is_all_close());
+        assert_eq!(result.count_violations, 2);
+
+        let first = result.first.unwrap();
+        assert_eq!(first.index, vec![0, 1]);
+        assert_eq!((first.x, first.y), (200.0, 203.0));
+
+        let worst = result.worst.unwrap();
+        assert_eq!(worst.index, vec![1, 0]);
+        assert_eq!((worst.x, worst.y), (3.0, 3.5));
+
+        let result = allclose(&a, &b, tols, NanComparison::NotEqual).unwrap();
+        assert_eq!(result.count_violations, 3);
+        assert!(result.worst.unwrap().ratio.is_infinite());
+
+        let result = allclose(&a, &a.mapv(|x| x + 1e-4), tols, NanComparison::default()).unwrap();
+        assert!(result.is_all_close());
+        assert_eq!(result.first, None);
+
+        let result = allclose(
+            &a,
+            &b,
+            Tols {
+                atol: f64::NAN,
+                ..tols
+            },
+            NanComparison::default(),
+        );
+        assert!(matches!(result, Err(UniqueTolError::Tols(_))));
+    }
+}
@@ -5,12 +5,38 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 mod isapprox;
+mod isclose;
 mod uniquetol_1d;
+mod uniquetol_accumulator;
+#[cfg(feature = "complex")]
+mod uniquetol_complex;
+mod uniquetol_error;
+mod uniquetol_iter;
 mod uniquetol_nd;
+mod uniquetol_options;
+#[cfg(feature = "rayon")]
+mod uniquetol_par;
+mod uniquetol_points;
+mod uniquetol_scale;
 mod uniquetol_traits;
 
-pub use isapprox::{NanComparison, Tols};
-pub use uniquetol_1d::{Occurrence, UniqueTolResult};
-pub use uniquetol_nd::FlattenAxis;
+pub use isapprox::{ApproxEq, NanComparison, ToleranceRule, Tols, TolsError, isapprox};
+pub use isclose::{AllCloseResult, CloseViolation, allclose, isclose};
+pub use uniquetol_1d::{ClusterStrategy, Occurrence, OutputOrder, Representative, UniqueTolResult};
+pub use uniquetol_accumulator::UniqueTolAccumulator;
+#[cfg(feature = "complex")]
+pub use uniquetol_complex::isapprox_complex;
+pub use uniquetol_error::UniqueTolError;
+pub use uniquetol_iter::{UniqueTolIter, UniqueTolIterExt};
+pub use uniquetol_nd::{AxisBoundsError, FlattenAxis, UniqueTolNDResult};
+pub use uniquetol_options::{
+    UniqueTol, UniqueTolElement, UniqueTolInput, UniqueTolMetric, UniqueTolOptions,
+};
+pub use uniquetol_points::{Norm, uniquetol_points};
+pub use uniquetol_scale::DataScale;
 pub use uniquetol_traits::{UniqueTol1D, UniqueTolND};
@@ -6,37 +6,196 @@
 // copied, modified, or distributed except according to those terms.
 
 #[path = "test_arr.rs"]
-mod test_arr;
+pub(crate) mod test_arr;
 
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
 use num_traits::Float;
-use std::fmt::{Debug, Display};
 
-use crate::isapprox::{NanComparison, Tols, isapprox};
+use crate::isapprox::{ApproxEq, NanComparison};
+
+const LEN_ERR_MSG: &str = "Failed to convert cluster size to float";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Occurrence {
     #[default]
     Lowest,
     Highest,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct UniqueTolResult<F>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClusterStrategy {
+    #[default]
+    Anchor,
+    Chaining,
+    Complete,
+    // In axis mode, links slices within tolerance at every position at once rather than refining
+    // one position at a time, and groups those connected by links, so neither the order of the
+    // positions nor that of the slices affects the grouping. A single value is its own slice, so
+    // this otherwise behaves exactly like `Chaining`
+    Joint,
+}
+
+impl ClusterStrategy {
+    // `cluster` holds the indices into `arr` of the current cluster's members in sorted order,
+    // and is never empty. With a `monotone` comparison (see `ApproxEq::is_monotone`), complete
+    // linkage only checks the farthest member of each kind: the first, the first after its run
+    // (the farthest finite one when the run is infinite) and the last (a NaN, if any are)
+    #[inline]
+    pub(crate) fn joins_cluster<E, G>(
+        self,
+        arr: &[E],
+        cluster: &[usize],
+        val: E,
+        monotone: bool,
+        isapprox_fn: G,
+    ) -> bool
+    where
+        E: Copy + PartialEq,
+        G: Fn(E, E) -> bool,
+    {
+        match self {
+            ClusterStrategy::Anchor => isapprox_fn(arr[cluster[0]], val),
+            ClusterStrategy::Chaining | ClusterStrategy::Joint => {
+                isapprox_fn(arr[cluster[cluster.len() - 1]], val)
+            }
+            ClusterStrategy::Complete if monotone => {
+                let first = arr[cluster[0]];
+                let after_run = cluster.partition_point(|&idx| arr[idx] == first);
+
+                isapprox_fn(first, val)
+                    && cluster
+                        .get(after_run)
+                        .is_none_or(|&idx| isapprox_fn(arr[idx], val))
+                    && isapprox_fn(arr[cluster[cluster.len() - 1]], val)
+            }
+            ClusterStrategy::Complete => {
+                let last = arr[cluster[cluster.len() - 1]];
+
+                // The last member already passed against every other one, so a repeat of it
+                // only needs comparing with it
+                if val == last {
+                    return isapprox_fn(last, val);
+                }
+
+                // Equal values sit next to each other once sorted, so each run is checked once
+                let mut prev = None;
+                cluster.iter().all(|&idx| {
+                    let x = arr[idx];
+                    let skip = prev == Some(x);
+                    prev = Some(x);
+                    skip || isapprox_fn(x, val)
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Representative {
+    #[default]
+    Member,
+    Mean,
+    Median,
+    Midrange,
+}
+
+impl Representative {
+    // Returns `None` for `Member`, in which case the selected cluster member is kept as is
+    pub(crate) fn summarize<F>(self, vals: &mut [F]) -> Option<F>
+    where
+        F: Float + Display + Debug,
+    {
+        let n = vals.len();
+
+        match self {
+            Representative::Member => None,
+            Representative::Mean => {
+                // Running mean, so that clusters of values near `F::max_value()` do not overflow
+                let mean = vals.iter().enumerate().fold(F::zero(), |mean, (k, &x)| {
+                    mean + (x - mean) / F::from(k + 1).expect(LEN_ERR_MSG)
+                });
+                Some(mean)
+            }
+            Representative::Median => {
+                vals.sort_by(|x, y| x.partial_cmp(y).unwrap_or(core::cmp::Ordering::Equal));
+
+                match n % 2 {
+                    0 => Some(midpoint(vals[n / 2 - 1], vals[n / 2])),
+                    _ => Some(vals[n / 2]),
+                }
+            }
+            Representative::Midrange => {
+                let min_val = vals.iter().fold(vals[0], |acc, &x| acc.min(x));
+                let max_val = vals.iter().fold(vals[0], |acc, &x| acc.max(x));
+                Some(midpoint(min_val, max_val))
+            }
+        }
+    }
+}
+
+// Midpoint of `x <= y` that does not overflow for values near `F::max_value()`
+fn midpoint<F>(x: F, y: F) -> F
 where
     F: Float + Display + Debug,
 {
-    pub arr_unique: Vec<F>,
+    let two = F::one() + F::one();
+    let diff = y - x;
+
+    if diff.is_finite() {
+        x + diff / two
+    } else {
+        x / two + y / two
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputOrder {
+    #[default]
+    Sorted,
+    Stable,
+}
+
+// Returns the unique indices ordered by first appearance in the input, along with the inverse
+// permutation mapping each current unique index to its new position
+pub(crate) fn stable_order(
+    inverse_unique: &[usize],
+    len_unique: usize,
+) -> (Vec<usize>, Vec<usize>) {
+    let mut order = Vec::with_capacity(len_unique);
+    let mut rank = vec![usize::MAX; len_unique];
+
+    for &idx_unique in inverse_unique {
+        if rank[idx_unique] == usize::MAX {
+            rank[idx_unique] = order.len();
+            order.push(idx_unique);
+        }
+    }
+
+    (order, rank)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniqueTolResult<E> {
+    pub arr_unique: Vec<E>,
     pub indices_unique: Vec<usize>,
     pub inverse_unique: Vec<usize>,
     pub counts_unique: Vec<usize>,
 }
 
-impl<F> UniqueTolResult<F>
+impl<E> UniqueTolResult<E>
 where
-    F: Float + Display + Debug,
+    E: Copy,
 {
     #[inline]
-    pub fn remap_to_original(&self) -> Vec<F> {
+    pub fn remap_to_original(&self) -> Vec<E> {
         self.inverse_unique
             .iter()
             .map(|&idx| self.arr_unique[idx])
@@ -48,10 +207,100 @@ where
         self.arr_unique.len()
     }
 
+    // Each group is led by the index that stands for it, and the groups together cover every
+    // index of `arr` exactly once
+    #[cfg(feature = "complex")]
+    pub(crate) fn from_groups(arr: &[E], groups: &[Vec<usize>]) -> Self {
+        let indices_unique: Vec<usize> = groups.iter().map(|group| group[0]).collect();
+        let counts_unique = groups.iter().map(|group| group.len()).collect();
+        let mut inverse_unique = vec![0; arr.len()];
+
+        for (idx_unique, group) in groups.iter().enumerate() {
+            for &idx in group {
+                inverse_unique[idx] = idx_unique;
+            }
+        }
+
+        let arr_unique = indices_unique.iter().map(|&i| arr[i]).collect();
+
+        UniqueTolResult {
+            arr_unique,
+            indices_unique,
+            inverse_unique,
+            counts_unique,
+        }
+    }
+
+    pub(crate) fn reorder_stable(&mut self) {
+        let (order, rank) = stable_order(&self.inverse_unique, self.get_len_unique());
+
+        self.arr_unique = order.iter().map(|&idx| self.arr_unique[idx]).collect();
+        self.indices_unique = order.iter().map(|&idx| self.indices_unique[idx]).collect();
+        self.counts_unique = order.iter().map(|&idx| self.counts_unique[idx]).collect();
+
+        for idx_unique in self.inverse_unique.iter_mut() {
+            *idx_unique = rank[*idx_unique];
+        }
+    }
+
+    pub(crate) fn summarize_clusters<G>(&mut self, arr: &[E], summarize_fn: G)
+    where
+        G: Fn(&mut [E]) -> Option<E>,
+    {
+        let mut clusters: Vec<Vec<E>> = self
+            .counts_unique
+            .iter()
+            .map(|&cnt| Vec::with_capacity(cnt))
+            .collect();
+
+        for (&x, &idx_unique) in arr.iter().zip(self.inverse_unique.iter()) {
+            clusters[idx_unique].push(x);
+        }
+
+        for (val_unique, cluster) in self.arr_unique.iter_mut().zip(clusters.iter_mut()) {
+            if let Some(val) = summarize_fn(cluster) {
+                *val_unique = val;
+            }
+        }
+    }
+
+    // Applies the post-clustering options shared by every entry point, given how to summarize
+    // a cluster of `E`
+    pub(crate) fn finish<G>(
+        mut self,
+        arr: &[E],
+        representative: Representative,
+        order: OutputOrder,
+        summarize_fn: G,
+    ) -> Self
+    where
+        G: Fn(Representative, &mut [E]) -> Option<E>,
+    {
+        if representative != Representative::Member {
+            self.summarize_clusters(arr, |vals| summarize_fn(representative, vals));
+        }
+
+        if order == OutputOrder::Stable {
+            self.reorder_stable();
+        }
+
+        self
+    }
+
     #[inline]
     pub fn get_len_original(&self) -> usize {
         self.inverse_unique.len()
     }
+
+    // Swaps the unique values for the records in `items` that they were taken from
+    pub(crate) fn borrow_from<T>(self, items: &[T]) -> UniqueTolResult<&T> {
+        UniqueTolResult {
+            arr_unique: self.indices_unique.iter().map(|&idx| &items[idx]).collect(),
+            indices_unique: self.indices_unique,
+            inverse_unique: self.inverse_unique,
+            counts_unique: self.counts_unique,
+        }
+    }
 }
 
 pub fn sortperm<F>(arr: &[F], reverse: bool) -> Vec<usize>
@@ -60,81 +309,83 @@ where
 {
     let mut perm: Vec<usize> = (0..arr.len()).collect();
 
-    match reverse {
-        false => perm.sort_by(|&i, &j| {
-            arr[i]
-                .partial_cmp(&arr[j])
-                .unwrap_or(std::cmp::Ordering::Equal)
-        }),
-        true => perm.sort_by(|&i, &j| {
-            arr[j]
-                .partial_cmp(&arr[i])
-                .unwrap_or(std::cmp::Ordering::Equal)
-        }),
-    }
-
+    perm.sort_by(|&i, &j| cmp_nan_last(arr[i], arr[j], reverse));
     perm
 }
 
-pub fn uniquetol_1d<A, F>(
-    arr: A,
-    tols: Tols<F>,
-    nan_cmp: NanComparison,
-    occurrence: Occurrence,
-) -> UniqueTolResult<F>
+// Orders the non-NaN values ascending (or descending if `reverse`) with every NaN after them, so
+// that each cluster's members stay contiguous and NaNs always form a single run at the end
+#[inline]
+pub(crate) fn cmp_nan_last<F>(x: F, y: F, reverse: bool) -> Ordering
 where
-    A: AsRef<[F]>,
-    F: Float + Display + Debug,
+    F: Float,
 {
-    let arr = arr.as_ref();
-    let n = arr.len();
-
-    if n == 0 {
-        return UniqueTolResult {
-            arr_unique: Vec::new(),
-            indices_unique: Vec::new(),
-            inverse_unique: Vec::new(),
-            counts_unique: Vec::new(),
-        };
+    match (x.is_nan(), y.is_nan()) {
+        (false, false) if reverse => y.partial_cmp(&x).unwrap(),
+        (false, false) => x.partial_cmp(&y).unwrap(),
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
     }
+}
 
-    let perm_sorted = match occurrence {
-        Occurrence::Lowest => sortperm(arr, false),
-        Occurrence::Highest => sortperm(arr, true),
-    };
-
-    let mut indices_unique = Vec::with_capacity(n);
-    let mut inverse_unique = vec![0; n];
-    let mut counts_unique = Vec::with_capacity(n);
+// Returns the positions in `perm_sorted` at which each cluster begins
+pub fn cluster_starts<E, G>(
+    arr: &[E],
+    perm_sorted: &[usize],
+    strategy: ClusterStrategy,
+    monotone: bool,
+    isapprox_fn: G,
+) -> Vec<usize>
+where
+    E: Copy + PartialEq,
+    G: Fn(E, E) -> bool,
+{
+    if perm_sorted.is_empty() {
+        return Vec::new();
+    }
 
+    let mut starts = vec![0];
     let mut idx_curr = 0;
-    let mut cnt_curr: usize = 1;
-    let mut val_curr = arr[perm_sorted[0]];
 
     for (i, &idx) in perm_sorted.iter().enumerate().skip(1) {
-        let val = arr[idx];
-
-        if isapprox(val_curr, val, tols, nan_cmp) {
-            cnt_curr += 1;
-        } else {
-            indices_unique.push(perm_sorted[idx_curr]);
-            counts_unique.push(cnt_curr);
-
-            for j in 0..cnt_curr {
-                inverse_unique[perm_sorted[idx_curr + j]] = indices_unique.len() - 1;
-            }
+        let cluster = &perm_sorted[idx_curr..i];
 
+        if !strategy.joins_cluster(arr, cluster, arr[idx], monotone, &isapprox_fn) {
+            starts.push(i);
             idx_curr = i;
-            cnt_curr = 1;
-            val_curr = val;
         }
     }
 
-    indices_unique.push(perm_sorted[idx_curr]);
-    counts_unique.push(cnt_curr);
+    starts
+}
+
+pub fn uniquetol_sorted<E, G>(
+    arr: &[E],
+    perm_sorted: &[usize],
+    strategy: ClusterStrategy,
+    monotone: bool,
+    isapprox_fn: G,
+) -> UniqueTolResult<E>
+where
+    E: Copy + PartialEq,
+    G: Fn(E, E) -> bool,
+{
+    let n = arr.len();
+    let starts = cluster_starts(arr, perm_sorted, strategy, monotone, isapprox_fn);
+    let ends = starts.iter().skip(1).chain(core::iter::once(&n));
+
+    let mut indices_unique = Vec::with_capacity(starts.len());
+    let mut inverse_unique = vec![0; n];
+    let mut counts_unique = Vec::with_capacity(starts.len());
+
+    for (idx_unique, (&start, &end)) in starts.iter().zip(ends).enumerate() {
+        indices_unique.push(perm_sorted[start]);
+        counts_unique.push(end - start);
 
-    for j in 0..cnt_curr {
-        inverse_unique[perm_sorted[idx_curr + j]] = indices_unique.len() - 1;
+        for &idx in &perm_sorted[start..end] {
+            inverse_unique[idx] = idx_unique;
+        }
     }
 
     let arr_unique = indices_unique.iter().map(|&i| arr[i]).collect();
@@ -147,19 +398,56 @@ where
     }
 }
 
+pub(crate) fn uniquetol_1d<A, F, T>(
+    arr: A,
+    tols: T,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+) -> UniqueTolResult<F>
+where
+    A: AsRef<[F]>,
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    let arr = arr.as_ref();
+
+    let perm_sorted = match occurrence {
+        Occurrence::Lowest => sortperm(arr, false),
+        Occurrence::Highest => sortperm(arr, true),
+    };
+
+    let result = uniquetol_sorted(arr, &perm_sorted, strategy, tols.is_monotone(), |x, y| {
+        tols.isapprox(x, y, nan_cmp)
+    });
+
+    result.finish(arr, representative, order, Representative::summarize)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::isapprox::{ToleranceRule, Tols, isapprox};
     use test_arr::TEST_ARR;
 
-    fn test_uniquetol_1d(occurrence: Occurrence) {
+    fn test_uniquetol_1d(occurrence: Occurrence, strategy: ClusterStrategy) {
         let n = TEST_ARR.len();
         let k: usize = 179;
 
         let tols = Tols::default();
         let nan_cmp = NanComparison::default();
 
-        let uniquetol_arr = uniquetol_1d(&TEST_ARR, tols, nan_cmp, occurrence);
+        let uniquetol_arr = uniquetol_1d(
+            TEST_ARR,
+            tols,
+            nan_cmp,
+            occurrence,
+            strategy,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
 
         assert_eq!(uniquetol_arr.get_len_unique(), k);
         assert_eq!(uniquetol_arr.get_len_original(), n);
@@ -194,11 +482,339 @@ mod tests {
 
     #[test]
     fn test_uniquetol_1d_lowest() {
-        test_uniquetol_1d(Occurrence::Lowest);
+        test_uniquetol_1d(Occurrence::Lowest, ClusterStrategy::Anchor);
     }
 
     #[test]
     fn test_uniquetol_1d_highest() {
-        test_uniquetol_1d(Occurrence::Highest);
+        test_uniquetol_1d(Occurrence::Highest, ClusterStrategy::Anchor);
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complete() {
+        test_uniquetol_1d(Occurrence::Lowest, ClusterStrategy::Complete);
+        test_uniquetol_1d(Occurrence::Highest, ClusterStrategy::Complete);
+    }
+
+    #[test]
+    fn test_uniquetol_1d_drift() {
+        let arr = [0.0, 0.4, 0.8, 1.2, 1.6, 3.0];
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+        let occurrence = Occurrence::Lowest;
+        let representative = Representative::Member;
+
+        let result = uniquetol_1d(
+            arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Anchor,
+            representative,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![0.0, 0.8, 1.6, 3.0]);
+        assert_eq!(result.counts_unique, vec![2, 2, 1, 1]);
+
+        let result = uniquetol_1d(
+            arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Chaining,
+            representative,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![0.0, 3.0]);
+        assert_eq!(result.inverse_unique, vec![0, 0, 0, 0, 0, 1]);
+
+        let result = uniquetol_1d(
+            arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Complete,
+            representative,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![0.0, 0.8, 1.6, 3.0]);
+    }
+
+    #[test]
+    fn test_uniquetol_1d_representative() {
+        let arr = [1.0, 1.4, 1.1, 5.0, 5.4, 10.0];
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let tols_check = Tols::new(1e-12, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+        let occurrence = Occurrence::Lowest;
+        let strategy = ClusterStrategy::Anchor;
+
+        let expected = [
+            (Representative::Member, [1.0, 5.0, 10.0]),
+            (Representative::Mean, [3.5 / 3.0, 5.2, 10.0]),
+            (Representative::Median, [1.1, 5.2, 10.0]),
+            (Representative::Midrange, [1.2, 5.2, 10.0]),
+        ];
+
+        for (representative, arr_expected) in expected {
+            let result = uniquetol_1d(
+                arr,
+                tols,
+                nan_cmp,
+                occurrence,
+                strategy,
+                representative,
+                OutputOrder::Sorted,
+            );
+            assert_eq!(result.indices_unique, vec![0, 3, 5]);
+            assert!(
+                result
+                    .arr_unique
+                    .iter()
+                    .zip(arr_expected.iter())
+                    .all(|(&x, &y)| isapprox(x, y, tols_check, nan_cmp))
+            );
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_1d_representative_overflow() {
+        let tols = Tols::new(0.0, 1e-6).unwrap();
+        let nan_cmp = NanComparison::default();
+
+        let uniquetol_max = |arr: &[f64], representative| {
+            uniquetol_1d(
+                arr,
+                tols,
+                nan_cmp,
+                Occurrence::Lowest,
+                ClusterStrategy::Anchor,
+                representative,
+                OutputOrder::Sorted,
+            )
+            .arr_unique
+        };
+
+        let arr = [1e308; 3];
+        assert_eq!(uniquetol_max(&arr, Representative::Mean), vec![1e308]);
+
+        let arr = [f64::MAX, f64::MAX * 0.999999999];
+        for representative in [Representative::Median, Representative::Midrange] {
+            let arr_unique = uniquetol_max(&arr, representative);
+            assert_eq!(arr_unique.len(), 1);
+            assert!(arr_unique[0].is_finite());
+            assert!(arr_unique[0] >= arr[1] && arr_unique[0] <= arr[0]);
+        }
+
+        let arr = [-f64::MAX, -f64::MAX, f64::MAX];
+        let arr_unique = uniquetol_max(&arr, Representative::Mean);
+        assert!(arr_unique.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_uniquetol_1d_rule() {
+        let arr = [4.5, 3.0, 9.0];
+        let tols = Tols::new(0.5, 0.25).unwrap();
+        let uniquetol_rule = |rule| {
+            uniquetol_1d(
+                arr,
+                tols.with_rule(rule),
+                NanComparison::default(),
+                Occurrence::default(),
+                ClusterStrategy::default(),
+                Representative::default(),
+                OutputOrder::default(),
+            )
+        };
+
+        assert_eq!(
+            uniquetol_rule(ToleranceRule::Max).counts_unique,
+            vec![1, 1, 1]
+        );
+        assert_eq!(uniquetol_rule(ToleranceRule::Sum).counts_unique, vec![2, 1]);
+        assert_eq!(
+            uniquetol_rule(ToleranceRule::NumPy).counts_unique,
+            vec![2, 1]
+        );
+    }
+
+    #[test]
+    fn test_uniquetol_1d_stable() {
+        let arr = [3.0, 1.0, 3.0000000001, 2.0, 1.0];
+        let result = uniquetol_1d(
+            arr,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::Lowest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Stable,
+        );
+        assert_eq!(result.arr_unique, vec![3.0, 1.0, 2.0]);
+        assert_eq!(result.indices_unique, vec![0, 1, 3]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0, 2, 1]);
+        assert_eq!(result.counts_unique, vec![2, 2, 1]);
+        assert_eq!(result.remap_to_original(), vec![3.0, 1.0, 3.0, 2.0, 1.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_uniquetol_1d_serde() {
+        let result = uniquetol_1d(
+            [3.0, 1.0, 3.0000000001, 2.0, 1.0],
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::Highest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Stable,
+        );
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            serde_json::from_str::<UniqueTolResult<f64>>(&json).unwrap(),
+            result
+        );
+
+        let options = (
+            Occurrence::Highest,
+            ClusterStrategy::Complete,
+            Representative::Median,
+        );
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            serde_json::from_str::<(Occurrence, ClusterStrategy, Representative)>(&json).unwrap(),
+            options
+        );
+    }
+
+    struct WithinMidpoint(f64);
+
+    impl ApproxEq<f64> for WithinMidpoint {
+        // Non-transitive rule under which anchoring alone does not bound every pair
+        fn isapprox(&self, x: f64, y: f64, _nan_cmp: NanComparison) -> bool {
+            (x - y).abs() <= self.0 && (x.round() == y.round() || x == 0.0)
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complete_pairwise() {
+        let arr = [0.0, 0.4, 0.6, 0.9];
+        let nan_cmp = NanComparison::default();
+        let metric = WithinMidpoint(1.0);
+
+        let result = uniquetol_1d(
+            arr,
+            &metric,
+            nan_cmp,
+            Occurrence::Lowest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.counts_unique, vec![4]);
+
+        let result = uniquetol_1d(
+            arr,
+            &metric,
+            nan_cmp,
+            Occurrence::Lowest,
+            ClusterStrategy::Complete,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![0.0, 0.6]);
+        assert_eq!(result.counts_unique, vec![2, 2]);
+    }
+
+    // Forwards to `Tols` without claiming monotonicity, forcing the pairwise check
+    struct Pairwise(Tols<f64>);
+
+    impl ApproxEq<f64> for Pairwise {
+        fn isapprox(&self, x: f64, y: f64, nan_cmp: NanComparison) -> bool {
+            self.0.isapprox(x, y, nan_cmp)
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complete_monotone() {
+        let mut arr = TEST_ARR.to_vec();
+        arr.extend([-0.5, -0.45, 0.45, 0.5, f64::NAN, f64::NAN, f64::INFINITY]);
+        arr.extend(core::iter::repeat_n(0.25, 10_000));
+
+        for tols in [
+            Tols::new(0.1, 0.0).unwrap(),
+            Tols::new(0.0, 0.3).unwrap(),
+            Tols::new(0.05, 1.0).unwrap().with_rule(ToleranceRule::Sum),
+            Tols::new(0.0, 0.5).unwrap().with_rule(ToleranceRule::NumPy),
+            Tols::new(0.0, 0.0).unwrap().with_max_ulps(4),
+        ] {
+            assert!(ApproxEq::<f64>::is_monotone(&tols));
+
+            for occurrence in [Occurrence::Lowest, Occurrence::Highest] {
+                for nan_cmp in [NanComparison::Equal, NanComparison::NotEqual] {
+                    let result = uniquetol_1d(
+                        &arr,
+                        tols,
+                        nan_cmp,
+                        occurrence,
+                        ClusterStrategy::Complete,
+                        Representative::Member,
+                        OutputOrder::Sorted,
+                    );
+                    let expected = uniquetol_1d(
+                        &arr,
+                        Pairwise(tols),
+                        nan_cmp,
+                        occurrence,
+                        ClusterStrategy::Complete,
+                        Representative::Member,
+                        OutputOrder::Sorted,
+                    );
+                    assert_eq!(result.indices_unique, expected.indices_unique);
+                    assert_eq!(result.counts_unique, expected.counts_unique);
+                }
+            }
+        }
+
+        assert!(!ApproxEq::<f64>::is_monotone(&Tols::new(0.0, 1.5).unwrap()));
+    }
+
+    struct WithinAbove(f64);
+
+    impl ApproxEq<f64> for WithinAbove {
+        fn isapprox(&self, x: f64, y: f64, _nan_cmp: NanComparison) -> bool {
+            y >= x && y - x <= self.0
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_1d_custom_metric() {
+        let arr = [3.0, 1.0, 1.4, 2.2, 1.9, 3.3];
+        let nan_cmp = NanComparison::default();
+
+        let result = uniquetol_1d(
+            arr,
+            WithinAbove(0.5),
+            nan_cmp,
+            Occurrence::Lowest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![1.0, 1.9, 3.0]);
+        assert_eq!(result.inverse_unique, vec![2, 0, 0, 1, 1, 2]);
+        assert_eq!(result.counts_unique, vec![2, 2, 2]);
+
+        // Every element lies below the anchor when sorting in descending order
+        let result = uniquetol_1d(
+            arr,
+            WithinAbove(0.5),
+            nan_cmp,
+            Occurrence::Highest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.get_len_unique(), arr.len());
     }
 }
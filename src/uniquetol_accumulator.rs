@@ -0,0 +1,461 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use num_traits::Float;
+
+use crate::isapprox::{ApproxEq, NanComparison, Tols};
+use crate::uniquetol_1d::{Occurrence, UniqueTolResult};
+
+// The anchor is the cluster's first member in sorted order (its lowest under
+// `Occurrence::Lowest`, its highest under `Occurrence::Highest`) and stands for the cluster,
+// while `val_far` is its last member in that order
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cluster<F> {
+    anchor: F,
+    idx_anchor: usize,
+    val_far: F,
+    count: usize,
+    id: usize,
+}
+
+// Clusters are anchored as `uniquetol_1d` anchors them under `ClusterStrategy::Anchor`: each
+// value joins the cluster whose anchor comes last before it in sorted order if within tolerance
+// of that anchor, and otherwise anchors a cluster of its own. A value that arrives before an
+// existing cluster in sorted order takes over as its anchor when within tolerance of all of its
+// members, as it would have had it arrived first. Only the extent of each cluster is kept, so
+// when such a value is within tolerance of some members but not all (or the metric is not
+// monotone, see `ApproxEq::is_monotone`) it anchors a new cluster instead, whereas
+// `uniquetol_1d` would have moved the nearer members over to it. Values pushed in sorted order,
+// or chunks that do not interleave this way, always give the same result as `uniquetol_1d`.
+#[derive(Debug, Clone)]
+pub struct UniqueTolAccumulator<F, T = Tols<F>>
+where
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    tols: T,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    // Non-NaN clusters, kept sorted by anchor in ascending order
+    clusters: Vec<Cluster<F>>,
+    clusters_nan: Vec<Cluster<F>>,
+    // Cluster ID (in order of creation) of every value pushed so far
+    ids: Vec<usize>,
+    len_unique: usize,
+}
+
+impl<F, T> UniqueTolAccumulator<F, T>
+where
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    pub fn new(tols: T, nan_cmp: NanComparison, occurrence: Occurrence) -> Self {
+        UniqueTolAccumulator {
+            tols,
+            nan_cmp,
+            occurrence,
+            clusters: Vec::new(),
+            clusters_nan: Vec::new(),
+            ids: Vec::new(),
+            len_unique: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_len_unique(&self) -> usize {
+        self.len_unique
+    }
+
+    #[inline]
+    pub fn get_len_original(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn new_cluster(&mut self, val: F) -> Cluster<F> {
+        let cluster = Cluster {
+            anchor: val,
+            idx_anchor: self.ids.len(),
+            val_far: val,
+            count: 1,
+            id: self.len_unique,
+        };
+        self.len_unique += 1;
+        cluster
+    }
+
+    fn push_nan(&mut self, val: F) {
+        match self.nan_cmp {
+            NanComparison::Equal if !self.clusters_nan.is_empty() => {
+                self.clusters_nan[0].count += 1;
+                self.ids.push(self.clusters_nan[0].id);
+            }
+            _ => {
+                let cluster = self.new_cluster(val);
+                self.ids.push(cluster.id);
+                self.clusters_nan.push(cluster);
+            }
+        }
+    }
+
+    pub fn push(&mut self, val: F) {
+        if val.is_nan() {
+            self.push_nan(val);
+            return;
+        }
+
+        // The cluster whose anchor comes last before `val` in sorted order, if any, and the one
+        // whose anchor comes first after it
+        let (pos_before, pos_after, pos_insert) = match self.occurrence {
+            Occurrence::Lowest => {
+                let pos = self
+                    .clusters
+                    .partition_point(|cluster| cluster.anchor <= val);
+                (pos.checked_sub(1), pos, pos)
+            }
+            Occurrence::Highest => {
+                let pos = self
+                    .clusters
+                    .partition_point(|cluster| cluster.anchor < val);
+                (Some(pos), pos.wrapping_sub(1), pos)
+            }
+        };
+        let pos_before = pos_before.filter(|&p| p < self.clusters.len());
+        let pos_after = Some(pos_after).filter(|&p| p < self.clusters.len());
+
+        if let Some(p) = pos_before.filter(|&p| {
+            self.tols
+                .isapprox(self.clusters[p].anchor, val, self.nan_cmp)
+        }) {
+            let cluster = &mut self.clusters[p];
+            cluster.count += 1;
+
+            let is_farther = match self.occurrence {
+                Occurrence::Lowest => val > cluster.val_far,
+                Occurrence::Highest => val < cluster.val_far,
+            };
+
+            if is_farther {
+                cluster.val_far = val;
+            }
+
+            self.ids.push(cluster.id);
+            return;
+        }
+
+        // With a monotone metric, being within tolerance of the farthest member means being
+        // within tolerance of every member
+        let reanchor = pos_after.filter(|&p| {
+            self.tols.is_monotone()
+                && self
+                    .tols
+                    .isapprox(val, self.clusters[p].val_far, self.nan_cmp)
+        });
+
+        match reanchor {
+            Some(p) => {
+                let idx = self.ids.len();
+                let cluster = &mut self.clusters[p];
+                cluster.anchor = val;
+                cluster.idx_anchor = idx;
+                cluster.count += 1;
+                self.ids.push(cluster.id);
+            }
+            None => {
+                let cluster = self.new_cluster(val);
+                self.ids.push(cluster.id);
+                self.clusters.insert(pos_insert, cluster);
+            }
+        }
+    }
+
+    pub fn push_slice<A>(&mut self, chunk: A)
+    where
+        A: AsRef<[F]>,
+    {
+        let chunk = chunk.as_ref();
+        self.ids.reserve(chunk.len());
+
+        for &val in chunk {
+            self.push(val);
+        }
+    }
+
+    pub fn to_result(&self) -> UniqueTolResult<F> {
+        let clusters_sorted: Vec<&Cluster<F>> = match self.occurrence {
+            Occurrence::Lowest => self.clusters.iter().collect(),
+            Occurrence::Highest => self.clusters.iter().rev().collect(),
+        };
+
+        let mut rank = vec![0; self.len_unique];
+        let mut arr_unique = Vec::with_capacity(self.len_unique);
+        let mut indices_unique = Vec::with_capacity(self.len_unique);
+        let mut counts_unique = Vec::with_capacity(self.len_unique);
+
+        for cluster in clusters_sorted.into_iter().chain(self.clusters_nan.iter()) {
+            rank[cluster.id] = arr_unique.len();
+            arr_unique.push(cluster.anchor);
+            indices_unique.push(cluster.idx_anchor);
+            counts_unique.push(cluster.count);
+        }
+
+        UniqueTolResult {
+            arr_unique,
+            indices_unique,
+            inverse_unique: self.ids.iter().map(|&id| rank[id]).collect(),
+            counts_unique,
+        }
+    }
+
+    #[inline]
+    pub fn finalize(self) -> UniqueTolResult<F> {
+        self.to_result()
+    }
+}
+
+impl<F> Default for UniqueTolAccumulator<F, Tols<F>>
+where
+    F: Float + Display + Debug,
+{
+    fn default() -> Self {
+        Self::new(
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+        )
+    }
+}
+
+impl<F, T> Extend<F> for UniqueTolAccumulator<F, T>
+where
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = F>,
+    {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isapprox::isapprox;
+    use crate::uniquetol_1d::test_arr::TEST_ARR;
+    use crate::uniquetol_1d::{
+        ClusterStrategy, OutputOrder, Representative, sortperm, uniquetol_1d,
+    };
+
+    fn test_accumulator(occurrence: Occurrence) {
+        let tols = Tols::default();
+        let nan_cmp = NanComparison::default();
+
+        // Values pushed in sorted order are clustered exactly as in one batch
+        let perm = sortperm(&TEST_ARR, occurrence == Occurrence::Highest);
+        let arr: Vec<f64> = perm.iter().map(|&i| TEST_ARR[i]).collect();
+        let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, occurrence);
+        arr.chunks(100)
+            .for_each(|chunk| accumulator.push_slice(chunk));
+
+        let result_batch = uniquetol_1d(
+            &arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(accumulator.get_len_unique(), result_batch.get_len_unique());
+        assert_eq!(accumulator.finalize(), result_batch);
+
+        let chunks: Vec<&[f64]> = TEST_ARR.chunks(100).collect();
+        let k = chunks.len();
+
+        // In order, reversed, and strided through the chunks
+        let chunk_orders: [Vec<usize>; 3] = [
+            (0..k).collect(),
+            (0..k).rev().collect(),
+            (0..k).map(|i| (3 * i) % k).collect(),
+        ];
+
+        for chunk_order in chunk_orders {
+            let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, occurrence);
+            let mut arr = Vec::with_capacity(TEST_ARR.len());
+
+            for &i in &chunk_order {
+                accumulator.push_slice(chunks[i]);
+                arr.extend_from_slice(chunks[i]);
+            }
+
+            let len_unique = accumulator.get_len_unique();
+            let result = accumulator.finalize();
+            assert_eq!(result.get_len_unique(), len_unique);
+            assert_eq!(result.counts_unique.iter().sum::<usize>(), arr.len());
+
+            // Each cluster is led by its first member in sorted order, and every member is
+            // within tolerance of it
+            for (i, &x) in arr.iter().enumerate() {
+                let idx_unique = result.inverse_unique[i];
+                let anchor = result.arr_unique[idx_unique];
+                assert_eq!(arr[result.indices_unique[idx_unique]], anchor);
+                assert!(isapprox(anchor, x, tols, nan_cmp));
+
+                match occurrence {
+                    Occurrence::Lowest => assert!(anchor <= x),
+                    Occurrence::Highest => assert!(anchor >= x),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_accumulator_lowest() {
+        test_accumulator(Occurrence::Lowest);
+    }
+
+    #[test]
+    fn test_accumulator_highest() {
+        test_accumulator(Occurrence::Highest);
+    }
+
+    #[test]
+    fn test_accumulator_batch() {
+        let arr = [5.0, 1.0, 5.0001, 9.0, 1.0002, 0.9999, 9.0, 4.9998, 1.0];
+        let tols = Tols::new(1e-3, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+
+        for occurrence in [Occurrence::Lowest, Occurrence::Highest] {
+            let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, occurrence);
+            arr.chunks(4)
+                .for_each(|chunk| accumulator.push_slice(chunk));
+
+            let result_batch = uniquetol_1d(
+                arr,
+                tols,
+                nan_cmp,
+                occurrence,
+                ClusterStrategy::Anchor,
+                Representative::Member,
+                OutputOrder::Sorted,
+            );
+            assert_eq!(accumulator.to_result(), result_batch);
+        }
+    }
+
+    #[test]
+    fn test_accumulator_arrival_order() {
+        // Values arriving between two existing clusters must not widen either of them
+        let arr = [0.0, 0.6, 1.2, 0.3, 0.9];
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+
+        let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, Occurrence::Lowest);
+        accumulator.extend(arr);
+        let result = accumulator.finalize();
+
+        assert_eq!(result.arr_unique, vec![0.0, 0.6, 1.2]);
+        assert_eq!(result.counts_unique, vec![2, 2, 1]);
+        assert_eq!(
+            result,
+            uniquetol_1d(
+                arr,
+                tols,
+                nan_cmp,
+                Occurrence::Lowest,
+                ClusterStrategy::Anchor,
+                Representative::Member,
+                OutputOrder::Sorted,
+            )
+        );
+    }
+
+    #[test]
+    fn test_accumulator_reanchor() {
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+
+        // A value arriving below a cluster and within tolerance of all of its members takes
+        // over as its anchor
+        let arr = [1.0, 1.3, 0.9];
+        let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, Occurrence::Lowest);
+        accumulator.extend(arr);
+        assert_eq!(accumulator.get_len_unique(), 1);
+        assert_eq!(
+            accumulator.finalize(),
+            uniquetol_1d(
+                arr,
+                tols,
+                nan_cmp,
+                Occurrence::Lowest,
+                ClusterStrategy::Anchor,
+                Representative::Member,
+                OutputOrder::Sorted,
+            )
+        );
+
+        // One within tolerance of only some of them anchors a cluster of its own, where the
+        // batch result would have 1.0 join it
+        let arr = [1.0, 1.3, 0.7];
+        let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, Occurrence::Lowest);
+        accumulator.extend(arr);
+        let result = accumulator.finalize();
+        assert_eq!(result.arr_unique, vec![0.7, 1.0]);
+        assert_eq!(result.counts_unique, vec![1, 2]);
+
+        let result = uniquetol_1d(
+            arr,
+            tols,
+            nan_cmp,
+            Occurrence::Lowest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+        assert_eq!(result.arr_unique, vec![0.7, 1.3]);
+        assert_eq!(result.counts_unique, vec![2, 1]);
+
+        // The same holds in reverse under `Occurrence::Highest`
+        let mut accumulator = UniqueTolAccumulator::new(tols, nan_cmp, Occurrence::Highest);
+        accumulator.extend([1.0, 0.7, 1.1]);
+        let result = accumulator.finalize();
+        assert_eq!(result.arr_unique, vec![1.1]);
+        assert_eq!(result.indices_unique, vec![2]);
+        assert_eq!(result.counts_unique, vec![3]);
+    }
+
+    #[test]
+    fn test_accumulator_nan() {
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let chunks: [&[f64]; 2] = [&[1.0, f64::NAN, 3.0], &[0.8, f64::NAN, 3.2]];
+
+        let mut accumulator =
+            UniqueTolAccumulator::new(tols, NanComparison::Equal, Occurrence::Lowest);
+        chunks
+            .iter()
+            .for_each(|chunk| accumulator.push_slice(chunk));
+        let result = accumulator.finalize();
+
+        assert_eq!(result.indices_unique, vec![3, 2, 1]);
+        assert_eq!(result.inverse_unique, vec![0, 2, 1, 0, 2, 1]);
+        assert_eq!(result.counts_unique, vec![2, 2, 2]);
+
+        let mut accumulator =
+            UniqueTolAccumulator::new(tols, NanComparison::NotEqual, Occurrence::Lowest);
+        accumulator.extend(chunks.iter().flat_map(|chunk| chunk.iter().copied()));
+        let result = accumulator.finalize();
+
+        assert_eq!(result.indices_unique, vec![3, 2, 1, 4]);
+        assert_eq!(result.inverse_unique, vec![0, 2, 1, 0, 3, 1]);
+    }
+}
@@ -0,0 +1,546 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use ndarray::{Array2, ArrayBase, ArrayView, Data, Dimension, IxDyn};
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::isapprox::{ApproxEq, NanComparison, Tols, isapprox_ulps};
+use crate::uniquetol_1d::{
+    ClusterStrategy, Occurrence, OutputOrder, Representative, UniqueTolResult, cmp_nan_last,
+};
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{
+    FlattenAxis, UniqueTolNDResult, check_flatten_axis, flatten_logical, shape_slice,
+    uniquetol_nd_flatten_axis_by, uniquetol_nd_flatten_dims_by, uniquetol_nd_flatten_joint_by,
+};
+use crate::uniquetol_points::cluster_rows;
+
+#[inline]
+pub fn isapprox_complex<F>(
+    z: Complex<F>,
+    w: Complex<F>,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+) -> bool
+where
+    F: Float + Display + Debug,
+{
+    let z_is_nan = z.re.is_nan() || z.im.is_nan();
+    let w_is_nan = w.re.is_nan() || w.im.is_nan();
+
+    if z_is_nan && w_is_nan {
+        return nan_cmp.into();
+    }
+
+    if z_is_nan || w_is_nan {
+        return false;
+    }
+
+    if z == w {
+        return true;
+    }
+
+    let max_ulps = tols.max_ulps;
+    (z - w).norm() <= tols.tol(z.norm(), w.norm())
+        || (isapprox_ulps(z.re, w.re, max_ulps) && isapprox_ulps(z.im, w.im, max_ulps))
+}
+
+impl<F> ApproxEq<Complex<F>> for Tols<F>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn isapprox(&self, z: Complex<F>, w: Complex<F>, nan_cmp: NanComparison) -> bool {
+        isapprox_complex(z, w, *self, nan_cmp)
+    }
+}
+
+// Lexicographic comparison of real and then imaginary parts, with NaNs placed last
+fn cmp_complex<F>(z: Complex<F>, w: Complex<F>, reverse: bool) -> Ordering
+where
+    F: Float + Display + Debug,
+{
+    cmp_nan_last(z.re, w.re, reverse).then(cmp_nan_last(z.im, w.im, reverse))
+}
+
+// Clusters the values as points `(re, im)` in the plane, so that they are compared by the
+// modulus of their difference just as `isapprox_complex` does, rather than one part at a time.
+// Leaders are taken in lexicographic order, and each group lists its leader first
+fn cluster_complex<F>(
+    arr: &[Complex<F>],
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+    strategy: ClusterStrategy,
+    reverse: bool,
+) -> Vec<Vec<usize>>
+where
+    F: Float + Display + Debug,
+{
+    let points = Array2::from_shape_fn((arr.len(), 2), |(i, j)| match j {
+        0 => arr[i].re,
+        _ => arr[i].im,
+    });
+    let magnitudes: Vec<F> = arr.iter().map(|z| z.norm()).collect();
+    let mut perm: Vec<usize> = (0..arr.len()).collect();
+    perm.sort_by(|&i, &j| cmp_complex(arr[i], arr[j], reverse));
+
+    cluster_rows(
+        &points.view(),
+        &perm,
+        &magnitudes,
+        tols,
+        strategy,
+        |i, j| isapprox_complex(arr[i], arr[j], tols, nan_cmp),
+    )
+}
+
+// Summarizes the real and imaginary parts separately, which for `Mean` coincides with the
+// complex mean and for `Median` yields the marginal median
+fn summarize_complex<F>(
+    representative: Representative,
+    vals: &mut [Complex<F>],
+) -> Option<Complex<F>>
+where
+    F: Float + Display + Debug,
+{
+    let mut vals_re: Vec<F> = vals.iter().map(|z| z.re).collect();
+    let mut vals_im: Vec<F> = vals.iter().map(|z| z.im).collect();
+    let re = representative.summarize(&mut vals_re)?;
+    let im = representative.summarize(&mut vals_im)?;
+    Some(Complex::new(re, im))
+}
+
+pub fn uniquetol_1d_complex<A, F>(
+    arr: A,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+) -> UniqueTolResult<Complex<F>>
+where
+    A: AsRef<[Complex<F>]>,
+    F: Float + Display + Debug,
+{
+    let arr = arr.as_ref();
+
+    let groups = cluster_complex(
+        arr,
+        tols,
+        nan_cmp,
+        strategy,
+        occurrence == Occurrence::Highest,
+    );
+    let result = UniqueTolResult::from_groups(arr, &groups);
+
+    result.finish(arr, representative, order, summarize_complex)
+}
+
+// Clusters each column exactly as `uniquetol_1d_complex` does, so that slices are compared by
+// modulus rather than by a box around their real and imaginary parts. Members are listed in
+// lexicographic order, as `uniquetol_groups` lists them in sorted order
+fn uniquetol_groups_complex<F>(
+    group: &[usize],
+    arr: &[Complex<F>],
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+    strategy: ClusterStrategy,
+) -> Vec<Vec<usize>>
+where
+    F: Float + Display + Debug,
+{
+    let mut groups = cluster_complex(arr, tols, nan_cmp, strategy, false);
+
+    for members in groups.iter_mut() {
+        members.sort_by(|&i, &j| cmp_complex(arr[i], arr[j], false).then(i.cmp(&j)));
+        members.iter_mut().for_each(|i| *i = group[*i]);
+    }
+
+    groups
+}
+
+#[allow(clippy::too_many_arguments)]
+fn uniquetol_nd_flatten_axes_complex<F, G>(
+    arr: &ArrayView<Complex<F>, IxDyn>,
+    tols_at: G,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: &FlattenAxis,
+) -> UniqueTolNDResult<Complex<F>>
+where
+    F: Float + Display + Debug,
+    G: Fn(usize) -> Tols<F>,
+{
+    let refine_fn = |j, group: &[usize], sub_arr: &[Complex<F>]| {
+        uniquetol_groups_complex(group, sub_arr, tols_at(j), nan_cmp, strategy)
+    };
+    let result = match flatten_axis {
+        _ if strategy == ClusterStrategy::Joint => uniquetol_nd_flatten_joint_by(
+            arr,
+            occurrence,
+            flatten_axis,
+            |pos, z, w| isapprox_complex(z, w, tols_at(pos), nan_cmp),
+            |z, w| cmp_complex(z, w, false),
+        ),
+        FlattenAxis::Dims(axes) => uniquetol_nd_flatten_dims_by(arr, occurrence, axes, refine_fn),
+        _ => {
+            uniquetol_nd_flatten_axis_by(arr, occurrence, flatten_axis.get_axis_unique(), refine_fn)
+        }
+    };
+
+    result.finish(arr, representative, order, summarize_complex)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn uniquetol_nd_complex<F, S, D>(
+    arr: &ArrayBase<S, D>,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> Result<UniqueTolNDResult<Complex<F>>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug,
+    S: Data<Elem = Complex<F>>,
+    D: Dimension,
+{
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
+    match flatten_axis {
+        FlattenAxis::None => {
+            let arr_flat = flatten_logical(arr);
+            let result = uniquetol_1d_complex(
+                &*arr_flat,
+                tols,
+                nan_cmp,
+                occurrence,
+                strategy,
+                representative,
+                order,
+            );
+            Ok(UniqueTolNDResult::from_flat(result, arr.shape()))
+        }
+        flatten_axis => Ok(uniquetol_nd_flatten_axes_complex(
+            arr,
+            |_| tols,
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            &flatten_axis,
+        )),
+    }
+}
+
+// The complex counterpart of `uniquetol_nd_columnwise`, with one tolerance per slice position
+#[allow(clippy::too_many_arguments)]
+pub fn uniquetol_nd_complex_columnwise<F, S, D, St, Dt>(
+    arr: &ArrayBase<S, D>,
+    tols: &ArrayBase<St, Dt>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> Result<UniqueTolNDResult<Complex<F>>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug,
+    S: Data<Elem = Complex<F>>,
+    D: Dimension,
+    St: Data<Elem = Tols<F>>,
+    Dt: Dimension,
+{
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
+    let shape_expected = shape_slice(arr.shape(), &flatten_axis);
+
+    if tols.shape() != shape_expected.as_slice() {
+        return Err(UniqueTolError::TolsShape(
+            shape_expected,
+            tols.shape().to_vec(),
+        ));
+    }
+
+    let tols_flat = flatten_logical(&tols.view().into_dyn());
+    tols_flat.iter().try_for_each(|tols| tols.validate())?;
+
+    match flatten_axis {
+        FlattenAxis::None => uniquetol_nd_complex(
+            arr,
+            tols_flat[0],
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            FlattenAxis::None,
+        ),
+        flatten_axis => Ok(uniquetol_nd_flatten_axes_complex(
+            arr,
+            |j| tols_flat[j],
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            &flatten_axis,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isapprox::ToleranceRule;
+    use crate::uniquetol_options::UniqueTol;
+    use crate::uniquetol_traits::UniqueTol1D;
+    use ndarray::prelude::*;
+
+    const TOLS: Tols<f64> = Tols {
+        atol: 1e-5,
+        rtol: 1e-2,
+        max_ulps: 0,
+        rule: ToleranceRule::Max,
+    };
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    #[test]
+    fn test_isapprox_complex() {
+        let nan_cmp = NanComparison::default();
+        assert!(isapprox_complex(
+            c(1.0, 1.0),
+            c(1.005, 0.995),
+            TOLS,
+            nan_cmp
+        ));
+        assert!(!isapprox_complex(c(1.0, 1.0), c(1.0, -1.0), TOLS, nan_cmp));
+        assert!(isapprox_complex(
+            c(f64::NAN, 0.0),
+            c(0.0, f64::NAN),
+            TOLS,
+            NanComparison::Equal
+        ));
+        assert!(!isapprox_complex(
+            c(f64::NAN, 0.0),
+            c(f64::NAN, 0.0),
+            TOLS,
+            NanComparison::NotEqual
+        ));
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complex() {
+        // Eigenvalues of a real matrix come in conjugate pairs, which must not be merged
+        let arr = [
+            c(2.0, 3.0),
+            c(-1.0, 0.0),
+            c(2.000001, -3.000002),
+            c(1.999999, 3.000001),
+            c(2.0, -3.0),
+            c(-1.000001, 0.0),
+        ];
+        let nan_cmp = NanComparison::default();
+        let result = uniquetol_1d_complex(
+            arr,
+            TOLS,
+            nan_cmp,
+            Occurrence::Lowest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+        );
+        assert_eq!(
+            arr.uniquetol(&UniqueTol::new().tols(TOLS).occurrence(Occurrence::Lowest)),
+            result
+        );
+
+        assert_eq!(
+            result.arr_unique,
+            vec![c(-1.000001, 0.0), c(1.999999, 3.000001), c(2.0, -3.0)]
+        );
+        assert_eq!(result.indices_unique, vec![5, 3, 4]);
+        assert_eq!(result.inverse_unique, vec![1, 0, 2, 1, 2, 0]);
+        assert_eq!(result.counts_unique, vec![2, 2, 2]);
+
+        let result = uniquetol_1d_complex(
+            arr,
+            TOLS,
+            nan_cmp,
+            Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+        );
+        assert_eq!(result.indices_unique, vec![2, 0, 1]);
+        assert_eq!(result.counts_unique, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complex_modulus() {
+        // The values 100 units above the real axis are within tolerance of each other, but
+        // blocking by real part puts 1.0 with 0.0 and 1.01 with 1.02, and sorting each block by
+        // imaginary part then split them into four clusters
+        let arr = [c(0.0, 0.0), c(1.0, 100.0), c(1.01, 0.0), c(1.02, 100.0)];
+        let tols = Tols::new(1.0, 0.0).unwrap();
+
+        for strategy in [
+            ClusterStrategy::Anchor,
+            ClusterStrategy::Chaining,
+            ClusterStrategy::Complete,
+            ClusterStrategy::Joint,
+        ] {
+            let result = uniquetol_1d_complex(
+                arr,
+                tols,
+                NanComparison::default(),
+                Occurrence::Lowest,
+                strategy,
+                Representative::default(),
+                OutputOrder::default(),
+            );
+            assert_eq!(result.indices_unique, vec![0, 1, 2]);
+            assert_eq!(result.inverse_unique, vec![0, 1, 2, 1]);
+            assert_eq!(result.counts_unique, vec![1, 2, 1]);
+
+            // The same values as the columns of a matrix are grouped the same way
+            let arr_2d = Array2::from_shape_vec((1, 4), arr.to_vec()).unwrap();
+            let result = uniquetol_nd_complex(
+                &arr_2d,
+                tols,
+                NanComparison::default(),
+                Occurrence::Lowest,
+                strategy,
+                Representative::default(),
+                OutputOrder::default(),
+                FlattenAxis::Dim(1),
+            )
+            .unwrap();
+            assert_eq!(result.inverse_unique, vec![0, 1, 2, 1]);
+            assert_eq!(result.counts_unique, vec![1, 2, 1]);
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_1d_complex_mean() {
+        let arr = [c(1.0, 1.0), c(1.000002, 0.999998), c(-2.0, 0.5)];
+        let result = uniquetol_1d_complex(
+            arr,
+            TOLS,
+            NanComparison::default(),
+            Occurrence::Lowest,
+            ClusterStrategy::default(),
+            Representative::Mean,
+            OutputOrder::default(),
+        );
+        assert_eq!(result.indices_unique, vec![2, 0]);
+        assert!(isapprox_complex(
+            result.arr_unique[1],
+            c(1.000001, 0.999999),
+            Tols::new(1e-12, 0.0).unwrap(),
+            NanComparison::default()
+        ));
+    }
+
+    #[test]
+    fn test_uniquetol_nd_complex_0() {
+        let arr = array![
+            [c(1.0, 1.0), c(0.0, -2.0)],
+            [c(3.0, 0.0), c(0.0, -2.0)],
+            [c(1.000001, 0.999999), c(0.0, -2.000001)],
+        ]
+        .into_dyn();
+        let result = uniquetol_nd_complex(
+            &arr,
+            TOLS,
+            NanComparison::default(),
+            Occurrence::Lowest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+
+        assert_eq!(result.indices_unique, vec![2, 1]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0]);
+        assert_eq!(result.counts_unique, vec![2, 1]);
+        assert_eq!(result.remap_to_original().shape(), arr.shape());
+    }
+
+    #[test]
+    fn test_uniquetol_nd_complex_none() {
+        let arr = array![
+            [c(1.0, 1.0), c(0.0, -2.0)],
+            [c(0.0, -2.000001), c(1.0, 1.0)]
+        ];
+        let result = uniquetol_nd_complex(
+            &arr.into_dyn(),
+            TOLS,
+            NanComparison::default(),
+            Occurrence::Lowest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::None,
+        )
+        .unwrap();
+        let expected = array![c(0.0, -2.000001), c(1.0, 1.0)];
+        assert_eq!(result.arr_unique, expected.into_dyn());
+        assert_eq!(result.inverse_unique, vec![1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_uniquetol_nd_complex_column() {
+        // The imaginary parts differ by far more than 1% of each other, yet the difference is
+        // well within 1% of the modulus
+        let arr = array![
+            [c(100.0, 0.001)],
+            [c(100.0, 0.5)],
+            [c(-3.0, 4.0)],
+            [c(-3.02, 4.01)],
+            [c(7.0, 0.0)]
+        ];
+
+        let run = |flatten_axis| {
+            uniquetol_nd_complex(
+                &arr,
+                TOLS,
+                NanComparison::default(),
+                Occurrence::default(),
+                ClusterStrategy::default(),
+                Representative::default(),
+                OutputOrder::Stable,
+                flatten_axis,
+            )
+            .unwrap()
+        };
+        let result_none = run(FlattenAxis::None);
+        let result_dim = run(FlattenAxis::Dim(0));
+
+        assert_eq!(result_none.inverse_unique, vec![0, 0, 1, 1, 2]);
+        assert_eq!(result_dim.inverse_unique, result_none.inverse_unique);
+        assert_eq!(result_dim.indices_unique, result_none.indices_unique);
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use ndarray::ShapeError;
+use num_traits::Float;
+
+use crate::isapprox::TolsError;
+use crate::uniquetol_nd::AxisBoundsError;
+
+#[derive(Debug)]
+pub enum UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    AxisBounds(AxisBoundsError),
+    DataScale(F),
+    DuplicateAxis(usize),
+    EmptyAxis(usize),
+    NoAxes,
+    NormOrder(F),
+    Shape(ShapeError),
+    Tols(TolsError<F>),
+    TolsShape(Vec<usize>, Vec<usize>),
+}
+
+impl<F> Display for UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            UniqueTolError::AxisBounds(err) => write!(f, "{}", err),
+            UniqueTolError::DataScale(scale) => {
+                write!(
+                    f,
+                    "Data scale must be finite and non-negative, got {}",
+                    scale
+                )
+            }
+            UniqueTolError::DuplicateAxis(axis) => {
+                write!(f, "Axis {} is listed more than once", axis)
+            }
+            UniqueTolError::EmptyAxis(axis) => {
+                write!(
+                    f,
+                    "Axis {} has length zero, so there are no slices to compare",
+                    axis
+                )
+            }
+            UniqueTolError::NoAxes => {
+                write!(f, "FlattenAxis::Dims must list at least one axis")
+            }
+            UniqueTolError::NormOrder(p) => {
+                write!(
+                    f,
+                    "Minkowski order must be finite and at least 1, got {}",
+                    p
+                )
+            }
+            UniqueTolError::Shape(err) => write!(f, "{}", err),
+            UniqueTolError::Tols(err) => write!(f, "{}", err),
+            UniqueTolError::TolsShape(expected, found) => {
+                write!(
+                    f,
+                    "Expected one value per slice position with shape {:?}, got shape {:?}",
+                    expected, found
+                )
+            }
+        }
+    }
+}
+
+impl<F> core::error::Error for UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            UniqueTolError::AxisBounds(err) => Some(err),
+            UniqueTolError::DataScale(_) => None,
+            UniqueTolError::DuplicateAxis(_) => None,
+            UniqueTolError::EmptyAxis(_) => None,
+            UniqueTolError::NoAxes => None,
+            UniqueTolError::NormOrder(_) => None,
+            // ndarray only implements `Error` for its shape errors with its `std` feature
+            #[cfg(feature = "std")]
+            UniqueTolError::Shape(err) => Some(err),
+            #[cfg(not(feature = "std"))]
+            UniqueTolError::Shape(_) => None,
+            UniqueTolError::Tols(_) => None,
+            UniqueTolError::TolsShape(_, _) => None,
+        }
+    }
+}
+
+impl<F> From<AxisBoundsError> for UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    fn from(err: AxisBoundsError) -> Self {
+        UniqueTolError::AxisBounds(err)
+    }
+}
+
+impl<F> From<ShapeError> for UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    fn from(err: ShapeError) -> Self {
+        UniqueTolError::Shape(err)
+    }
+}
+
+impl<F> From<TolsError<F>> for UniqueTolError<F>
+where
+    F: Float + Display + Debug,
+{
+    fn from(err: TolsError<F>) -> Self {
+        UniqueTolError::Tols(err)
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use core::iter::FusedIterator;
+use num_traits::Float;
+
+use crate::isapprox::{ApproxEq, NanComparison, Tols};
+use crate::uniquetol_1d::UniqueTolResult;
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_options::{UniqueTolElement, UniqueTolMetric, UniqueTolOptions};
+
+// Yields the first value of each anchor cluster. This only matches `uniquetol_1d` when the input
+// is already sorted; otherwise, only consecutive runs of approximately equal values collapse.
+#[derive(Debug, Clone)]
+pub struct UniqueTolIter<I, F, T = Tols<F>>
+where
+    I: Iterator<Item = F>,
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    iter: I,
+    tols: T,
+    nan_cmp: NanComparison,
+    anchor: Option<F>,
+}
+
+impl<I, F, T> UniqueTolIter<I, F, T>
+where
+    I: Iterator<Item = F>,
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    // The metric is checked here, since nothing is compared until the first call to `next`
+    pub fn new(iter: I, tols: T) -> Result<Self, UniqueTolError<F>>
+    where
+        T: UniqueTolMetric<F>,
+    {
+        tols.validate()?;
+
+        Ok(UniqueTolIter {
+            iter,
+            tols,
+            nan_cmp: NanComparison::default(),
+            anchor: None,
+        })
+    }
+
+    #[inline]
+    pub fn with_nan_cmp(self, nan_cmp: NanComparison) -> Self {
+        UniqueTolIter { nan_cmp, ..self }
+    }
+}
+
+impl<I, F, T> Iterator for UniqueTolIter<I, F, T>
+where
+    I: Iterator<Item = F>,
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+    type Item = F;
+
+    fn next(&mut self) -> Option<F> {
+        for val in self.iter.by_ref() {
+            let joins_anchor = self
+                .anchor
+                .is_some_and(|anchor| self.tols.isapprox(anchor, val, self.nan_cmp));
+
+            if !joins_anchor {
+                self.anchor = Some(val);
+                return Some(val);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+
+        // Only the very first value is guaranteed to start a new cluster
+        match self.anchor {
+            None => (lower.min(1), upper),
+            Some(_) => (0, upper),
+        }
+    }
+}
+
+impl<I, F, T> FusedIterator for UniqueTolIter<I, F, T>
+where
+    I: FusedIterator<Item = F>,
+    F: Float + Display + Debug,
+    T: ApproxEq<F>,
+{
+}
+
+pub trait UniqueTolIterExt<F>: Iterator<Item = F> + Sized
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn unique_tol<T>(self, tols: T) -> Result<UniqueTolIter<Self, F, T>, UniqueTolError<F>>
+    where
+        T: ApproxEq<F> + UniqueTolMetric<F>,
+    {
+        UniqueTolIter::new(self, tols)
+    }
+
+    // Collects the whole input first, so it need not be sorted
+    fn collect_unique_tol<T>(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<F>, UniqueTolError<F>>
+    where
+        F: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        let arr: Vec<F> = self.collect();
+        options.run(&arr)
+    }
+}
+
+impl<I, F> UniqueTolIterExt<F> for I
+where
+    I: Iterator<Item = F>,
+    F: Float + Display + Debug,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uniquetol_1d::test_arr::TEST_ARR;
+    use crate::uniquetol_1d::{
+        ClusterStrategy, Occurrence, OutputOrder, Representative, uniquetol_1d,
+    };
+    use crate::uniquetol_options::UniqueTol;
+    use crate::uniquetol_scale::DataScale;
+    use alloc::vec;
+
+    #[test]
+    fn test_unique_tol_sorted() {
+        let mut arr = TEST_ARR.to_vec();
+        arr.sort_by(|x, y| x.partial_cmp(y).unwrap());
+
+        let tols = Tols::default();
+        let expected = uniquetol_1d(
+            &arr,
+            tols,
+            NanComparison::default(),
+            Occurrence::Lowest,
+            ClusterStrategy::Anchor,
+            Representative::Member,
+            OutputOrder::Sorted,
+        );
+
+        let result: Vec<f64> = arr.iter().copied().unique_tol(tols).unwrap().collect();
+        assert_eq!(result, expected.arr_unique);
+    }
+
+    #[test]
+    fn test_unique_tol_lazy() {
+        let tols = Tols::new(0.1, 0.0).unwrap();
+
+        // The input is unbounded, so anything but a lazy adapter would never return
+        let result: Vec<f64> = (0..)
+            .map(|i| f64::from(i / 3) + 0.01 * f64::from(i % 3))
+            .unique_tol(tols)
+            .unwrap()
+            .take(4)
+            .collect();
+        assert_eq!(result, vec![0.0, 1.0, 2.0, 3.0]);
+
+        let arr = [1.0, 1.05, f64::NAN, f64::NAN];
+        let result: Vec<f64> = arr.into_iter().unique_tol(tols).unwrap().collect();
+        assert_eq!(result.len(), 2);
+
+        let iter = arr
+            .into_iter()
+            .unique_tol(tols)
+            .unwrap()
+            .with_nan_cmp(NanComparison::NotEqual);
+        assert_eq!(iter.size_hint(), (1, Some(4)));
+        assert_eq!(iter.count(), 3);
+
+        // Invalid tolerances are caught before anything is compared, even for an empty input
+        let mut tols_bad = tols;
+        tols_bad.rtol = f64::NAN;
+        let result = core::iter::empty::<f64>().unique_tol(tols_bad);
+        assert!(matches!(result, Err(UniqueTolError::Tols(_))));
+    }
+
+    #[test]
+    fn test_collect_unique_tol() {
+        let arr = [3.0, 1.0, 3.0000000001, 2.0, 1.0];
+
+        let result: Vec<f64> = arr
+            .into_iter()
+            .unique_tol(Tols::default())
+            .unwrap()
+            .collect();
+        assert_eq!(result, vec![3.0, 1.0, 3.0000000001, 2.0, 1.0]);
+
+        let options = UniqueTol::new().order(OutputOrder::Stable);
+        let result = arr.into_iter().collect_unique_tol(&options).unwrap();
+        assert_eq!(result.arr_unique, vec![3.0, 1.0, 2.0]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0, 2, 1]);
+
+        // Every option applies, including those that need the whole input at once
+        let options = options
+            .tols(Tols::new(0.0, 0.4).unwrap())
+            .data_scale(DataScale::Auto);
+        let result = arr.into_iter().collect_unique_tol(&options).unwrap();
+        assert_eq!(result.arr_unique, vec![3.0, 1.0]);
+
+        let result = arr.into_iter().collect_unique_tol(&options.atol(-1.0));
+        assert!(matches!(result, Err(UniqueTolError::Tols(_))));
+    }
+}
@@ -5,15 +5,28 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use ndarray::{Array, Axis, IxDyn};
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::ops::Index;
+use ndarray::iter::LanesMut;
+use ndarray::{
+    Array, ArrayBase, ArrayView, Axis, Data, Dimension, IxDyn, RemoveAxis, ShapeError, indices,
+};
 use num_traits::Float;
-use std::fmt::{Debug, Display};
 
-use crate::isapprox::{NanComparison, Tols, isapprox};
-use crate::uniquetol_1d::{Occurrence, sortperm, uniquetol_1d};
+use crate::isapprox::{ApproxEq, NanComparison};
+use crate::uniquetol_1d::{
+    ClusterStrategy, Occurrence, OutputOrder, Representative, UniqueTolResult, cmp_nan_last,
+    sortperm, stable_order, uniquetol_1d,
+};
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_options::UniqueTolMetric;
 
 const SHAPE_ERR_MSG: &str = "Failed to reshape vector to ndarray";
-const CONTIG_ERR_MSG: &str = "Array is not contiguous";
+const DIM_ERR_MSG: &str = "Failed to restore the dimensionality of remapped values";
 
 #[derive(Debug)]
 pub struct AxisBoundsError {
@@ -22,7 +35,7 @@ pub struct AxisBoundsError {
 }
 
 impl Display for AxisBoundsError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "Axis {} is out of bounds for array with {} dimensions",
@@ -31,37 +44,418 @@ impl Display for AxisBoundsError {
     }
 }
 
-impl std::error::Error for AxisBoundsError {}
+impl core::error::Error for AxisBoundsError {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlattenAxis {
     #[default]
     None,
     Dim(usize),
+    Dims(Vec<usize>),
 }
 
-fn uniquetol_groups<F>(
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniqueTolNDResult<E, D = IxDyn>
+where
+    D: Dimension,
+{
+    pub arr_unique: Array<E, D>,
+    pub indices_unique: Vec<usize>,
+    pub inverse_unique: Vec<usize>,
+    pub counts_unique: Vec<usize>,
+    pub flatten_axis: FlattenAxis,
+    pub shape_original: Vec<usize>,
+}
+
+impl<E> UniqueTolNDResult<E>
+where
+    E: Copy,
+{
+    pub(crate) fn from_flat(result: UniqueTolResult<E>, shape_original: &[usize]) -> Self {
+        let shape = IxDyn(&[result.arr_unique.len()]);
+
+        UniqueTolNDResult {
+            arr_unique: Array::from_shape_vec(shape, result.arr_unique).expect(SHAPE_ERR_MSG),
+            indices_unique: result.indices_unique,
+            inverse_unique: result.inverse_unique,
+            counts_unique: result.counts_unique,
+            flatten_axis: FlattenAxis::None,
+            shape_original: shape_original.to_vec(),
+        }
+    }
+
+    pub(crate) fn from_groups(
+        arr: &ArrayView<E, IxDyn>,
+        groups: &[Vec<usize>],
+        occurrence: Occurrence,
+        flatten_axis: FlattenAxis,
+    ) -> Self {
+        let indices_unique: Vec<usize> = groups
+            .iter()
+            .map(|group| match occurrence {
+                Occurrence::Lowest => group[0],
+                Occurrence::Highest => group[group.len() - 1],
+            })
+            .collect();
+        let counts_unique = groups.iter().map(|group| group.len()).collect();
+        let mut inverse_unique = vec![0; groups.iter().map(|group| group.len()).sum()];
+
+        for (idx_unique, group) in groups.iter().enumerate() {
+            for &idx in group {
+                inverse_unique[idx] = idx_unique;
+            }
+        }
+
+        let arr_unique = match &flatten_axis {
+            FlattenAxis::Dims(axes) => select_rows(arr, axes, &indices_unique),
+            _ => arr.select(Axis(flatten_axis.get_axis_unique()), &indices_unique),
+        };
+
+        UniqueTolNDResult {
+            arr_unique,
+            indices_unique,
+            inverse_unique,
+            counts_unique,
+            flatten_axis,
+            shape_original: arr.shape().to_vec(),
+        }
+    }
+
+    pub(crate) fn reorder_stable(&mut self) {
+        let (order, rank) = stable_order(&self.inverse_unique, self.get_len_unique());
+        let axis = self.flatten_axis.get_axis_unique();
+
+        self.arr_unique = self.arr_unique.select(Axis(axis), &order);
+        self.indices_unique = order.iter().map(|&idx| self.indices_unique[idx]).collect();
+        self.counts_unique = order.iter().map(|&idx| self.counts_unique[idx]).collect();
+
+        for idx_unique in self.inverse_unique.iter_mut() {
+            *idx_unique = rank[*idx_unique];
+        }
+    }
+
+    pub(crate) fn summarize_slices<G>(&mut self, arr: &ArrayView<E, IxDyn>, summarize_fn: G)
+    where
+        G: Fn(&mut [E]) -> Option<E>,
+    {
+        let mut groups: Vec<Vec<usize>> = self
+            .counts_unique
+            .iter()
+            .map(|&cnt| Vec::with_capacity(cnt))
+            .collect();
+
+        for (idx, &idx_unique) in self.inverse_unique.iter().enumerate() {
+            groups[idx_unique].push(idx);
+        }
+
+        let lanes_unique = self
+            .arr_unique
+            .lanes_mut(Axis(self.flatten_axis.get_axis_unique()));
+
+        match &self.flatten_axis {
+            FlattenAxis::Dims(axes) => {
+                summarize_columns(&groups, row_columns(arr, axes), lanes_unique, summarize_fn)
+            }
+            flatten_axis => {
+                let lanes = arr.lanes(Axis(flatten_axis.get_axis_unique()));
+                summarize_columns(&groups, lanes.into_iter(), lanes_unique, summarize_fn)
+            }
+        }
+    }
+
+    // Applies the post-clustering options shared by every entry point, given how to summarize
+    // the values at one slice position
+    pub(crate) fn finish<G>(
+        mut self,
+        arr: &ArrayView<E, IxDyn>,
+        representative: Representative,
+        order: OutputOrder,
+        summarize_fn: G,
+    ) -> Self
+    where
+        G: Fn(Representative, &mut [E]) -> Option<E>,
+    {
+        if representative != Representative::Member {
+            self.summarize_slices(arr, |vals| summarize_fn(representative, vals));
+        }
+
+        if order == OutputOrder::Stable {
+            self.reorder_stable();
+        }
+
+        self
+    }
+}
+
+impl<E, D> UniqueTolNDResult<E, D>
+where
+    E: Copy,
+    D: RemoveAxis,
+{
+    // Results of a dynamic dimensionality are restored to the input's shape exactly, as are
+    // `FlattenAxis::Dim` results of any dimensionality. Flat and combined-axis results fixed to a
+    // static dimensionality keep their unique values or slices laid out along one axis instead.
+    pub fn remap_to_original(&self) -> Array<E, D> {
+        let axis_unique = Axis(self.flatten_axis.get_axis_unique());
+
+        let arr_remapped = match &self.flatten_axis {
+            FlattenAxis::Dim(_) => {
+                return self.arr_unique.select(axis_unique, &self.inverse_unique);
+            }
+            FlattenAxis::None => {
+                let vals_unique: Vec<E> = self.arr_unique.iter().copied().collect();
+                let vals: Vec<E> = self
+                    .inverse_unique
+                    .iter()
+                    .map(|&idx| vals_unique[idx])
+                    .collect();
+
+                match D::NDIM {
+                    Some(_) => Array::from_vec(vals).into_dyn(),
+                    None => Array::from_shape_vec(IxDyn(&self.shape_original), vals)
+                        .expect(SHAPE_ERR_MSG),
+                }
+            }
+            FlattenAxis::Dims(axes) => {
+                let arr_rows = self.arr_unique.select(axis_unique, &self.inverse_unique);
+
+                if D::NDIM.is_some() {
+                    return arr_rows;
+                }
+
+                self.split_rows(arr_rows.into_dyn(), axes)
+            }
+        };
+
+        arr_remapped.into_dimensionality().expect(DIM_ERR_MSG)
+    }
+
+    // Splits the combined row axis back into its constituent axes before restoring the original
+    // axis order
+    fn split_rows(&self, arr_rows: Array<E, IxDyn>, axes: &[usize]) -> Array<E, IxDyn> {
+        let axis_unique = self.flatten_axis.get_axis_unique();
+        let perm = rows_first(&[axis_unique], arr_rows.ndim());
+        let arr_rows = arr_rows.permuted_axes(perm);
+
+        let perm = rows_first(axes, self.shape_original.len());
+        let shape_rows: Vec<usize> = perm.iter().map(|&a| self.shape_original[a]).collect();
+        let arr_rows = arr_rows
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order(shape_rows)
+            .expect(SHAPE_ERR_MSG);
+
+        let mut perm_inv = vec![0; perm.len()];
+        for (j, &a) in perm.iter().enumerate() {
+            perm_inv[a] = j;
+        }
+
+        arr_rows
+            .permuted_axes(perm_inv)
+            .as_standard_layout()
+            .into_owned()
+    }
+}
+
+impl<E, D> UniqueTolNDResult<E, D>
+where
+    D: Dimension,
+{
+    #[inline]
+    pub fn get_len_unique(&self) -> usize {
+        self.indices_unique.len()
+    }
+
+    #[inline]
+    pub fn get_len_original(&self) -> usize {
+        self.inverse_unique.len()
+    }
+
+    // Always succeeds for `Ix1` after `FlattenAxis::None` and for the input's own dimension type
+    // after `FlattenAxis::Dim`
+    pub fn into_dimensionality<D2>(self) -> Result<UniqueTolNDResult<E, D2>, ShapeError>
+    where
+        D2: Dimension,
+    {
+        Ok(UniqueTolNDResult {
+            arr_unique: self.arr_unique.into_dimensionality()?,
+            indices_unique: self.indices_unique,
+            inverse_unique: self.inverse_unique,
+            counts_unique: self.counts_unique,
+            flatten_axis: self.flatten_axis,
+            shape_original: self.shape_original,
+        })
+    }
+}
+
+impl FlattenAxis {
+    // The axis of `arr_unique` along which the unique values or slices are laid out. Combined
+    // axes collapse into one at the position of the lowest of them
+    pub(crate) fn get_axis_unique(&self) -> usize {
+        match self {
+            FlattenAxis::None => 0,
+            FlattenAxis::Dim(axis) => *axis,
+            FlattenAxis::Dims(axes) => axes.iter().copied().min().unwrap_or(0),
+        }
+    }
+}
+
+fn summarize_columns<E, C, I, G>(
+    groups: &[Vec<usize>],
+    columns: I,
+    lanes_unique: LanesMut<E, IxDyn>,
+    summarize_fn: G,
+) where
+    E: Copy,
+    C: Index<usize, Output = E>,
+    I: Iterator<Item = C>,
+    G: Fn(&mut [E]) -> Option<E>,
+{
+    let mut vals = Vec::new();
+
+    // Each column holds one position of every slice, so no slice is ever copied out whole
+    for (column, mut lane_unique) in columns.zip(lanes_unique) {
+        for (group, val_unique) in groups.iter().zip(lane_unique.iter_mut()) {
+            vals.clear();
+            vals.extend(group.iter().map(|&idx| column[idx]));
+
+            if let Some(val) = summarize_fn(&mut vals) {
+                *val_unique = val;
+            }
+        }
+    }
+}
+
+// Axis permutation moving `axes` (in the given order) to the front, followed by the rest
+fn rows_first(axes: &[usize], ndim: usize) -> Vec<usize> {
+    axes.iter()
+        .copied()
+        .chain((0..ndim).filter(|axis| !axes.contains(axis)))
+        .collect()
+}
+
+// The values of every combined row at each position of the remaining axes, in logical order
+pub(crate) fn row_columns<'a, E>(
+    arr: &ArrayView<'a, E, IxDyn>,
+    axes: &[usize],
+) -> impl Iterator<Item = Vec<E>> + 'a
+where
+    E: Copy,
+{
+    let m = axes.len();
+    let arr_rows = arr.clone().permuted_axes(rows_first(axes, arr.ndim()));
+
+    indices(&arr_rows.shape()[m..])
+        .into_iter()
+        .map(move |idx_rest| {
+            let mut column = arr_rows.clone();
+
+            for (j, &i) in idx_rest.slice().iter().enumerate().rev() {
+                column.index_axis_inplace(Axis(m + j), i);
+            }
+
+            column.iter().copied().collect()
+        })
+}
+
+// Unravels the combined row index in row-major order over the leading axes of `arr_rows`
+fn row_view<'a, E>(
+    arr_rows: &ArrayView<'a, E, IxDyn>,
+    shape_rows: &[usize],
+    idx: usize,
+) -> ArrayView<'a, E, IxDyn> {
+    let mut row = arr_rows.clone();
+    let mut stride = shape_rows.iter().product::<usize>();
+
+    for &len in shape_rows {
+        stride /= len;
+        row.index_axis_inplace(Axis(0), idx / stride % len);
+    }
+
+    row
+}
+
+// Views of every slice being compared, in the order `inverse_unique` refers to them
+fn slice_views<'a, E>(
+    arr: &ArrayView<'a, E, IxDyn>,
+    flatten_axis: &FlattenAxis,
+) -> Vec<ArrayView<'a, E, IxDyn>> {
+    match flatten_axis {
+        FlattenAxis::Dims(axes) => {
+            let arr_rows = arr.clone().permuted_axes(rows_first(axes, arr.ndim()));
+            let shape_rows = &arr_rows.shape()[..axes.len()];
+            let k = shape_rows.iter().product();
+            (0..k)
+                .map(|idx| row_view(&arr_rows, shape_rows, idx))
+                .collect()
+        }
+        _ => {
+            let axis = Axis(flatten_axis.get_axis_unique());
+            (0..arr.len_of(axis))
+                .map(|idx| arr.clone().index_axis_move(axis, idx))
+                .collect()
+        }
+    }
+}
+
+fn select_rows<E>(
+    arr: &ArrayView<E, IxDyn>,
+    axes: &[usize],
+    indices_rows: &[usize],
+) -> Array<E, IxDyn>
+where
+    E: Copy,
+{
+    let arr_rows = arr.clone().permuted_axes(rows_first(axes, arr.ndim()));
+    let shape_rows = &arr_rows.shape()[..axes.len()];
+    let mut vals = Vec::new();
+
+    for &idx in indices_rows {
+        vals.extend(row_view(&arr_rows, shape_rows, idx).iter().copied());
+    }
+
+    let mut shape: Vec<usize> = arr_rows.shape()[axes.len()..].to_vec();
+    let axis_unique = axes.iter().copied().min().unwrap_or(0);
+    shape.insert(0, indices_rows.len());
+
+    let mut perm: Vec<usize> = (1..shape.len()).collect();
+    perm.insert(axis_unique, 0);
+
+    Array::from_shape_vec(IxDyn(&shape), vals)
+        .expect(SHAPE_ERR_MSG)
+        .permuted_axes(perm)
+        .as_standard_layout()
+        .into_owned()
+}
+
+pub fn uniquetol_groups<F, T>(
     group: &[usize],
     arr: &[F],
-    tols: Tols<F>,
+    tols: &T,
     nan_cmp: NanComparison,
+    strategy: ClusterStrategy,
 ) -> Vec<Vec<usize>>
 where
     F: Float + Display + Debug,
+    T: ApproxEq<F>,
 {
     let perm_sorted = sortperm(arr, false);
+    let monotone = tols.is_monotone();
     let mut groups = vec![vec![group[perm_sorted[0]]]];
-    let mut curr = arr[perm_sorted[0]];
+    let mut idx_curr = 0;
 
-    for &idx in perm_sorted.iter().skip(1) {
-        let next = arr[idx];
+    for (i, &idx) in perm_sorted.iter().enumerate().skip(1) {
+        let cluster = &perm_sorted[idx_curr..i];
 
-        match isapprox(curr, next, tols, nan_cmp) {
+        match strategy.joins_cluster(arr, cluster, arr[idx], monotone, |x, y| {
+            tols.isapprox(x, y, nan_cmp)
+        }) {
             // Safe to unwrap: groups is always initialized with one element
             true => groups.last_mut().unwrap().push(group[idx]),
             false => {
                 groups.push(vec![group[idx]]);
-                curr = next;
+                idx_curr = i;
             }
         }
     }
@@ -69,90 +463,383 @@ where
     groups
 }
 
-#[inline]
-fn uniquetol_nd_flatten_none<F>(
-    arr: &Array<F, IxDyn>,
-    tols: Tols<F>,
-    nan_cmp: NanComparison,
-    occurrence: Occurrence,
-) -> Array<F, IxDyn>
+// Shape of a single slice, made up of the axes not taken out by `flatten_axis`
+pub(crate) fn shape_slice(shape: &[usize], flatten_axis: &FlattenAxis) -> Vec<usize> {
+    let axes = match flatten_axis {
+        FlattenAxis::None => return Vec::new(),
+        FlattenAxis::Dim(axis) => core::slice::from_ref(axis),
+        FlattenAxis::Dims(axes) => axes.as_slice(),
+    };
+
+    (0..shape.len())
+        .filter(|axis| !axes.contains(axis))
+        .map(|axis| shape[axis])
+        .collect()
+}
+
+// Rejects axes that are out of bounds, repeated or empty before any mode-specific work is done
+pub(crate) fn check_flatten_axis<E, F>(
+    arr: &ArrayView<E, IxDyn>,
+    flatten_axis: &FlattenAxis,
+) -> Result<(), UniqueTolError<F>>
 where
     F: Float + Display + Debug,
 {
-    let arr_flat = arr.as_slice().expect(CONTIG_ERR_MSG);
-    let arr_unique = uniquetol_1d(arr_flat, tols, nan_cmp, occurrence).arr_unique;
-    let shape = IxDyn(&[arr_unique.len()]);
-    Array::from_shape_vec(shape, arr_unique).expect(SHAPE_ERR_MSG)
+    let axes = match flatten_axis {
+        FlattenAxis::None => return Ok(()),
+        FlattenAxis::Dim(axis) => core::slice::from_ref(axis),
+        // Collapsing no axes would treat the whole array as one slice under a spurious new axis
+        FlattenAxis::Dims(axes) if axes.is_empty() => return Err(UniqueTolError::NoAxes),
+        FlattenAxis::Dims(axes) => axes.as_slice(),
+    };
+
+    for (i, &axis) in axes.iter().enumerate() {
+        if axis >= arr.ndim() {
+            return Err(UniqueTolError::AxisBounds(AxisBoundsError {
+                axis,
+                ndim: arr.ndim(),
+            }));
+        } else if axes[..i].contains(&axis) {
+            return Err(UniqueTolError::DuplicateAxis(axis));
+        } else if arr.len_of(Axis(axis)) == 0 {
+            return Err(UniqueTolError::EmptyAxis(axis));
+        }
+    }
+
+    Ok(())
+}
+
+// Borrows the data directly when it is already laid out in logical (row-major) order, only
+// gathering a copy for transposed, strided or column-major arrays
+pub(crate) fn flatten_logical<'a, E>(arr: &ArrayView<'a, E, IxDyn>) -> Cow<'a, [E]>
+where
+    E: Clone,
+{
+    match arr.to_slice() {
+        Some(arr_flat) => Cow::Borrowed(arr_flat),
+        None => Cow::Owned(arr.iter().cloned().collect()),
+    }
 }
 
-fn uniquetol_nd_flatten_axis<F>(
-    arr: &Array<F, IxDyn>,
-    tols: Tols<F>,
+#[inline]
+fn uniquetol_nd_flatten_none<F, T>(
+    arr: &ArrayView<F, IxDyn>,
+    tols: T,
     nan_cmp: NanComparison,
     occurrence: Occurrence,
-    axis: usize,
-) -> Array<F, IxDyn>
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+) -> UniqueTolNDResult<F>
 where
     F: Float + Display + Debug,
+    T: ApproxEq<F>,
 {
-    let arr_flat: Vec<F> = arr
-        .axis_iter(Axis(axis))
-        .flat_map(|slice| slice.to_owned())
-        .collect();
+    let arr_flat = flatten_logical(arr);
+    let result = uniquetol_1d(
+        &*arr_flat,
+        tols,
+        nan_cmp,
+        occurrence,
+        strategy,
+        representative,
+        order,
+    );
+    UniqueTolNDResult::from_flat(result, arr.shape())
+}
 
-    let k = arr.len_of(Axis(axis));
-    let n = arr.len() / k;
+// Splits the rows into groups one column at a time, where column `j` holds position `j` of
+// every row and is passed to `refine_fn` along with `j` itself
+pub(crate) fn refine_columns<E, C, I, G>(k: usize, columns: I, refine_fn: G) -> Vec<Vec<usize>>
+where
+    E: Copy,
+    C: Index<usize, Output = E>,
+    I: Iterator<Item = C>,
+    G: Fn(usize, &[usize], &[E]) -> Vec<Vec<usize>>,
+{
     let mut groups: Vec<Vec<usize>> = vec![(0..k).collect()];
     let mut groups_new = Vec::with_capacity(k);
-    let mut sub_arr = Vec::with_capacity(n);
+    let mut sub_arr = Vec::with_capacity(k);
 
-    for idx in 0..n {
+    for (j, column) in columns.enumerate() {
         groups_new.clear();
 
         for group in groups.iter() {
             sub_arr.clear();
-            sub_arr.extend(group.iter().map(|&i| arr_flat[i * n + idx]));
-            groups_new.extend(uniquetol_groups(group, &sub_arr, tols, nan_cmp));
+            sub_arr.extend(group.iter().map(|&i| column[i]));
+            groups_new.extend(refine_fn(j, group, &sub_arr));
         }
 
-        std::mem::swap(&mut groups, &mut groups_new);
+        core::mem::swap(&mut groups, &mut groups_new);
     }
 
-    let indices_unique: Vec<usize> = groups
-        .iter()
-        .map(|group| match occurrence {
-            Occurrence::Lowest => group[0],
-            Occurrence::Highest => group[group.len() - 1],
-        })
-        .collect();
-    arr.select(Axis(axis), &indices_unique)
+    groups
+}
+
+// Slices within tolerance of each other at every position (the Chebyshev criterion) are linked,
+// and each group gathers the slices connected through such links. Which pairs are linked depends
+// on neither the order of the slices nor that of the positions within them, so neither can
+// change the grouping
+fn joint_groups<E, G, H>(
+    slices: &[ArrayView<E, IxDyn>],
+    isapprox_fn: G,
+    cmp_fn: H,
+) -> Vec<Vec<usize>>
+where
+    E: Copy,
+    G: Fn(usize, E, E) -> bool,
+    H: Fn(E, E) -> Ordering,
+{
+    let k = slices.len();
+    let mut claimed = vec![false; k];
+    let mut groups = Vec::new();
+
+    let linked = |i: usize, j: usize| {
+        slices[i]
+            .iter()
+            .zip(slices[j].iter())
+            .enumerate()
+            .all(|(pos, (&x, &y))| isapprox_fn(pos, x, y))
+    };
+
+    for i in 0..k {
+        if claimed[i] {
+            continue;
+        }
+
+        claimed[i] = true;
+        let mut group = vec![i];
+        let mut next = 0;
+
+        // Every member of the group claims whatever it links to until nothing new is reached
+        while let Some(&member) = group.get(next) {
+            next += 1;
+
+            for (j, claimed_j) in claimed.iter_mut().enumerate() {
+                if !*claimed_j && linked(member, j) {
+                    *claimed_j = true;
+                    group.push(j);
+                }
+            }
+        }
+
+        groups.push(group);
+    }
+
+    // Sorting only afterwards keeps `Occurrence` and sorted output meaning what they do under
+    // column refinement without letting position order affect the grouping itself
+    let cmp_slices = |&i: &usize, &j: &usize| {
+        slices[i]
+            .iter()
+            .zip(slices[j].iter())
+            .map(|(&x, &y)| cmp_fn(x, y))
+            .find(|&ord| ord != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    };
+
+    for group in groups.iter_mut() {
+        group.sort_by(cmp_slices);
+    }
+
+    groups.sort_by(|group_x, group_y| cmp_slices(&group_x[0], &group_y[0]));
+    groups
+}
+
+pub fn uniquetol_nd_flatten_joint_by<E, G, H>(
+    arr: &ArrayView<E, IxDyn>,
+    occurrence: Occurrence,
+    flatten_axis: &FlattenAxis,
+    isapprox_fn: G,
+    cmp_fn: H,
+) -> UniqueTolNDResult<E>
+where
+    E: Copy,
+    G: Fn(usize, E, E) -> bool,
+    H: Fn(E, E) -> Ordering,
+{
+    let groups = joint_groups(&slice_views(arr, flatten_axis), isapprox_fn, cmp_fn);
+    UniqueTolNDResult::from_groups(arr, &groups, occurrence, flatten_axis.clone())
+}
+
+pub fn uniquetol_nd_flatten_axis_by<E, G>(
+    arr: &ArrayView<E, IxDyn>,
+    occurrence: Occurrence,
+    axis: usize,
+    refine_fn: G,
+) -> UniqueTolNDResult<E>
+where
+    E: Copy,
+    G: Fn(usize, &[usize], &[E]) -> Vec<Vec<usize>>,
+{
+    // Lanes along `axis` are visited in logical order, each one holding the same position of
+    // every slice
+    let k = arr.len_of(Axis(axis));
+    let groups = refine_columns(k, arr.lanes(Axis(axis)).into_iter(), refine_fn);
+    UniqueTolNDResult::from_groups(arr, &groups, occurrence, FlattenAxis::Dim(axis))
+}
+
+pub fn uniquetol_nd_flatten_dims_by<E, G>(
+    arr: &ArrayView<E, IxDyn>,
+    occurrence: Occurrence,
+    axes: &[usize],
+    refine_fn: G,
+) -> UniqueTolNDResult<E>
+where
+    E: Copy,
+    G: Fn(usize, &[usize], &[E]) -> Vec<Vec<usize>>,
+{
+    let k = axes.iter().map(|&axis| arr.len_of(Axis(axis))).product();
+    let groups = refine_columns(k, row_columns(arr, axes), refine_fn);
+    UniqueTolNDResult::from_groups(arr, &groups, occurrence, FlattenAxis::Dims(axes.to_vec()))
+}
+
+// `tols_at` gives the tolerance for each position of a slice, in logical order
+#[inline]
+#[allow(clippy::too_many_arguments)]
+fn uniquetol_nd_flatten_axes<'a, F, T, G>(
+    arr: &ArrayView<F, IxDyn>,
+    tols_at: G,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: &FlattenAxis,
+) -> UniqueTolNDResult<F>
+where
+    F: Float + Display + Debug,
+    T: ApproxEq<F> + 'a,
+    G: Fn(usize) -> &'a T,
+{
+    let refine_fn = |j: usize, group: &[usize], sub_arr: &[F]| {
+        uniquetol_groups(group, sub_arr, tols_at(j), nan_cmp, strategy)
+    };
+    let result = match (strategy, flatten_axis) {
+        (ClusterStrategy::Joint, _) => uniquetol_nd_flatten_joint_by(
+            arr,
+            occurrence,
+            flatten_axis,
+            |pos, x, y| tols_at(pos).isapprox(x, y, nan_cmp),
+            |x, y| cmp_nan_last(x, y, false),
+        ),
+        (_, FlattenAxis::Dims(axes)) => {
+            uniquetol_nd_flatten_dims_by(arr, occurrence, axes, refine_fn)
+        }
+        _ => {
+            uniquetol_nd_flatten_axis_by(arr, occurrence, flatten_axis.get_axis_unique(), refine_fn)
+        }
+    };
+
+    result.finish(arr, representative, order, Representative::summarize)
 }
 
-pub fn uniquetol_nd<F>(
-    arr: &Array<F, IxDyn>,
-    tols: Tols<F>,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn uniquetol_nd<F, T, S, D>(
+    arr: &ArrayBase<S, D>,
+    tols: T,
     nan_cmp: NanComparison,
     occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
     flatten_axis: FlattenAxis,
-) -> Result<Array<F, IxDyn>, AxisBoundsError>
+) -> Result<UniqueTolNDResult<F>, UniqueTolError<F>>
 where
     F: Float + Display + Debug,
+    T: ApproxEq<F>,
+    S: Data<Elem = F>,
+    D: Dimension,
 {
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
     match flatten_axis {
-        FlattenAxis::None => Ok(uniquetol_nd_flatten_none(arr, tols, nan_cmp, occurrence)),
-        FlattenAxis::Dim(axis) if axis < arr.ndim() => Ok(uniquetol_nd_flatten_axis(
-            arr, tols, nan_cmp, occurrence, axis,
+        FlattenAxis::None => Ok(uniquetol_nd_flatten_none(
+            arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+        )),
+        flatten_axis => Ok(uniquetol_nd_flatten_axes(
+            arr,
+            |_| &tols,
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            &flatten_axis,
+        )),
+    }
+}
+
+// `tols` holds one metric per position of a slice, so with `FlattenAxis::None` (where every
+// value is its own slice) it must be zero-dimensional
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn uniquetol_nd_columnwise<F, T, S, D, St, Dt>(
+    arr: &ArrayBase<S, D>,
+    tols: &ArrayBase<St, Dt>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> Result<UniqueTolNDResult<F>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug,
+    T: ApproxEq<F> + UniqueTolMetric<F>,
+    S: Data<Elem = F>,
+    D: Dimension,
+    St: Data<Elem = T>,
+    Dt: Dimension,
+{
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
+    let shape_expected = shape_slice(arr.shape(), &flatten_axis);
+
+    if tols.shape() != shape_expected.as_slice() {
+        return Err(UniqueTolError::TolsShape(
+            shape_expected,
+            tols.shape().to_vec(),
+        ));
+    }
+
+    let tols_flat = flatten_logical(&tols.view().into_dyn());
+    tols_flat.iter().try_for_each(|tols| tols.validate())?;
+
+    match flatten_axis {
+        FlattenAxis::None => uniquetol_nd(
+            arr,
+            tols_flat[0].clone(),
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            FlattenAxis::None,
+        ),
+        flatten_axis => Ok(uniquetol_nd_flatten_axes(
+            arr,
+            |j| &tols_flat[j],
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            &flatten_axis,
         )),
-        FlattenAxis::Dim(axis) => Err(AxisBoundsError {
-            axis,
-            ndim: arr.ndim(),
-        }),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::isapprox::{Tols, isapprox};
     use ndarray::prelude::*;
 
     const ARR_2D: [[f64; 3]; 4] = [
@@ -206,17 +893,17 @@ mod tests {
         let arr = arr_2d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::None,
         )
         .unwrap();
         let expected = array![-4.300000, -3.000001, 0.000000, 1.000000, 1.999996];
-        assert_eq!(result, &expected.into_dyn());
+        assert_eq!(result.arr_unique, expected.into_dyn());
     }
 
     #[test]
@@ -224,12 +911,12 @@ mod tests {
         let arr = arr_2d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::Dim(0),
         )
         .unwrap();
@@ -237,7 +924,278 @@ mod tests {
             [-4.300000, 1.999996, -0.000000],
             [1.000000, 2.000000, -3.000001],
         ];
-        assert_eq!(result, &expected.into_dyn());
+        assert_eq!(result.arr_unique, expected.into_dyn());
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_remap() {
+        let arr = arr_2d().into_dyn();
+        let tols = Tols::new(1e-5, 1e-2).unwrap();
+        let nan_cmp = NanComparison::default();
+        let result = uniquetol_nd(
+            &arr,
+            tols,
+            nan_cmp,
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        assert_eq!(result.indices_unique, vec![2, 0]);
+        assert_eq!(result.inverse_unique, vec![1, 1, 0, 1]);
+        assert_eq!(result.counts_unique, vec![1, 3]);
+
+        let arr_remapped = result.remap_to_original();
+        assert_eq!(arr_remapped.shape(), arr.shape());
+        assert!(
+            arr.iter()
+                .zip(arr_remapped.iter())
+                .all(|(&x, &y)| isapprox(x, y, tols, nan_cmp))
+        );
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_chaining() {
+        let arr = array![[0.0, 1.0], [0.8, 1.0], [0.4, 1.0], [0.4, 3.0]].into_dyn();
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+        let occurrence = Occurrence::default();
+        let flatten_axis = FlattenAxis::Dim(0);
+
+        let result = uniquetol_nd(
+            &arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Anchor,
+            Representative::default(),
+            OutputOrder::default(),
+            flatten_axis.clone(),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 2, 0, 1]);
+
+        let result = uniquetol_nd(
+            &arr,
+            tols,
+            nan_cmp,
+            occurrence,
+            ClusterStrategy::Chaining,
+            Representative::default(),
+            OutputOrder::default(),
+            flatten_axis,
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_joint() {
+        // Refining by the first column splits off the last row before the second column is seen,
+        // whereas refining by the second column first leaves it with the middle row
+        let arr = array![[0.0, 2.0], [1.0, 0.0], [2.0, 0.0]].into_dyn();
+        let arr_swapped = arr.slice(s![.., ..;-1]).into_owned().into_dyn();
+        let tols = Tols::new(1.0, 0.0).unwrap();
+        let uniquetol_2d_0 = |arr: &Array<f64, IxDyn>, strategy| {
+            uniquetol_nd(
+                arr,
+                tols,
+                NanComparison::default(),
+                Occurrence::default(),
+                strategy,
+                Representative::default(),
+                OutputOrder::default(),
+                FlattenAxis::Dim(0),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            uniquetol_2d_0(&arr, ClusterStrategy::Anchor).inverse_unique,
+            vec![1, 0, 2]
+        );
+        assert_eq!(
+            uniquetol_2d_0(&arr_swapped, ClusterStrategy::Anchor).inverse_unique,
+            vec![1, 0, 0]
+        );
+
+        let result = uniquetol_2d_0(&arr, ClusterStrategy::Joint);
+        assert_eq!(result.inverse_unique, vec![0, 1, 1]);
+        assert_eq!(result.indices_unique, vec![0, 1]);
+        assert_eq!(result.arr_unique, array![[0.0, 2.0], [1.0, 0.0]].into_dyn());
+
+        let result = uniquetol_2d_0(&arr_swapped, ClusterStrategy::Joint);
+        assert_eq!(result.inverse_unique, vec![1, 0, 0]);
+        assert_eq!(result.indices_unique, vec![1, 0]);
+
+        // The same holds when the rows span several axes
+        let arr_3d = arr.into_shape_with_order(IxDyn(&[3, 1, 2])).unwrap();
+        let result = uniquetol_nd(
+            &arr_3d,
+            tols,
+            NanComparison::default(),
+            Occurrence::Highest,
+            ClusterStrategy::Joint,
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::Dims(vec![1, 0]),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 1, 1]);
+        assert_eq!(result.indices_unique, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_joint_row_order() {
+        let tols = Tols::new(0.5, 0.0).unwrap();
+        let uniquetol_joint = |arr: &Array<f64, IxDyn>, flatten_axis| {
+            uniquetol_nd(
+                arr,
+                tols,
+                NanComparison::default(),
+                Occurrence::default(),
+                ClusterStrategy::Joint,
+                Representative::default(),
+                OutputOrder::default(),
+                flatten_axis,
+            )
+            .unwrap()
+        };
+
+        // 0.4 links 0.0 and 0.8 into one group whichever row comes first
+        let perms = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let rows = [0.4, 0.0, 0.8];
+
+        for perm in perms {
+            let arr = Array::from_shape_fn(IxDyn(&[3, 1]), |idx| rows[perm[idx[0]]]);
+            assert_eq!(
+                uniquetol_joint(&arr, FlattenAxis::Dim(0)).counts_unique,
+                vec![3]
+            );
+            assert_eq!(
+                uniquetol_joint(&arr, FlattenAxis::None).counts_unique,
+                vec![3]
+            );
+        }
+
+        // Which rows end up together, named by their position before permuting, is the same for
+        // every row order
+        let rows = array![[0.0, 0.0], [0.4, 0.3], [0.8, 0.1], [2.0, 0.0], [2.1, 0.9]];
+        let grouping = |perm: &[usize]| {
+            let arr = rows.select(Axis(0), perm).into_dyn();
+            let result = uniquetol_joint(&arr, FlattenAxis::Dim(0));
+            let mut grouping = vec![0; perm.len()];
+
+            for (i, &row) in perm.iter().enumerate() {
+                grouping[row] = perm
+                    .iter()
+                    .zip(result.inverse_unique.iter())
+                    .filter(|&(_, &idx_unique)| idx_unique == result.inverse_unique[i])
+                    .map(|(&other, _)| other)
+                    .min()
+                    .unwrap();
+            }
+
+            grouping
+        };
+
+        let expected = grouping(&[0, 1, 2, 3, 4]);
+        assert_eq!(expected, vec![0, 0, 0, 3, 4]);
+        for perm in [[4, 3, 2, 1, 0], [2, 0, 4, 1, 3], [1, 3, 0, 4, 2]] {
+            assert_eq!(grouping(&perm), expected);
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_mean() {
+        let arr = arr_2d().into_dyn();
+        let tols_check = Tols::new(1e-12, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+        let result = uniquetol_nd(
+            &arr,
+            Tols::new(1e-5, 1e-2).unwrap(),
+            nan_cmp,
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::Mean,
+            OutputOrder::default(),
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        let expected = array![
+            [-4.300000, 1.999996, -0.000000],
+            [1.000001, 2.000001, -8.999996 / 3.0],
+        ];
+        assert_eq!(result.indices_unique, vec![2, 0]);
+        assert!(
+            result
+                .arr_unique
+                .iter()
+                .zip(expected.iter())
+                .all(|(&x, &y)| isapprox(x, y, tols_check, nan_cmp))
+        );
+    }
+
+    #[test]
+    fn test_uniquetol_2d_0_stable() {
+        let arr = arr_2d().into_dyn();
+        let result = uniquetol_nd(
+            &arr,
+            Tols::new(1e-5, 1e-2).unwrap(),
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::Stable,
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        let expected = array![
+            [1.000000, 2.000000, -3.000001],
+            [-4.300000, 1.999996, -0.000000],
+        ];
+        assert_eq!(result.arr_unique, expected.into_dyn());
+        assert_eq!(result.indices_unique, vec![0, 2]);
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 0]);
+        assert_eq!(result.counts_unique, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_uniquetol_2d_none_remap() {
+        let arr = arr_2d().into_dyn();
+        let tols = Tols::new(1e-5, 1e-2).unwrap();
+        let nan_cmp = NanComparison::default();
+        let result = uniquetol_nd(
+            &arr,
+            tols,
+            nan_cmp,
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::None,
+        )
+        .unwrap();
+        assert_eq!(result.get_len_unique(), 5);
+        assert_eq!(result.get_len_original(), arr.len());
+        assert_eq!(result.counts_unique.iter().sum::<usize>(), arr.len());
+
+        let arr_remapped = result.remap_to_original();
+        assert_eq!(arr_remapped.shape(), arr.shape());
+        assert!(
+            arr.iter()
+                .zip(arr_remapped.iter())
+                .all(|(&x, &y)| isapprox(x, y, tols, nan_cmp))
+        );
     }
 
     #[test]
@@ -245,12 +1203,12 @@ mod tests {
         let arr = arr_2d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::Dim(1),
         )
         .unwrap();
@@ -260,7 +1218,7 @@ mod tests {
             [0.000000, -4.300000, 1.999996],
             [-2.999998, 1.000002, 2.000002],
         ];
-        assert_eq!(result, &expected.into_dyn());
+        assert_eq!(result.arr_unique, expected.into_dyn());
     }
 
     #[test]
@@ -268,19 +1226,19 @@ mod tests {
         let arr = arr_3d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::None,
         )
         .unwrap();
         let expected = array![
             2.000002, 1.000002, 0.000007, -2.999997, -3.777771, -4.299994
         ];
-        assert_eq!(result, &expected.into_dyn());
+        assert_eq!(result.arr_unique, expected.into_dyn());
     }
 
     #[test]
@@ -288,18 +1246,18 @@ mod tests {
         let arr = arr_3d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::Dim(0),
         )
         .unwrap();
         let shape_expected: [usize; 3] = SHAPE_3D.into();
-        assert_eq!(result.shape(), shape_expected);
-        println!("result: {:?}", result);
+        assert_eq!(result.arr_unique.shape(), shape_expected);
+        println!("result: {:?}", result.arr_unique);
     }
 
     #[test]
@@ -307,18 +1265,18 @@ mod tests {
         let arr = arr_3d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::Dim(1),
         )
         .unwrap();
         let shape_expected = [2, 3, 5];
-        assert_eq!(result.shape(), shape_expected);
-        println!("result: {:?}", result);
+        assert_eq!(result.arr_unique.shape(), shape_expected);
+        println!("result: {:?}", result.arr_unique);
     }
 
     #[test]
@@ -326,17 +1284,346 @@ mod tests {
         let arr = arr_3d();
         let result = uniquetol_nd(
             &arr.into_dyn(),
-            Tols {
-                atol: 1e-5,
-                rtol: 1e-2,
-            },
+            Tols::new(1e-5, 1e-2).unwrap(),
             NanComparison::default(),
             Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
             FlattenAxis::Dim(2),
         )
         .unwrap();
         let shape_expected = [2, 4, 3];
-        assert_eq!(result.shape(), shape_expected);
-        println!("result: {:?}", result);
+        assert_eq!(result.arr_unique.shape(), shape_expected);
+        println!("result: {:?}", result.arr_unique);
+    }
+
+    fn uniquetol_nd_default(
+        arr: &Array<f64, IxDyn>,
+        flatten_axis: FlattenAxis,
+    ) -> Result<UniqueTolNDResult<f64>, UniqueTolError<f64>> {
+        uniquetol_nd(
+            arr,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            flatten_axis,
+        )
+    }
+
+    #[test]
+    fn test_uniquetol_nd_errors() {
+        let arr = arr_2d().into_dyn();
+        assert!(matches!(
+            uniquetol_nd_default(&arr, FlattenAxis::Dim(2)),
+            Err(UniqueTolError::AxisBounds(AxisBoundsError {
+                axis: 2,
+                ndim: 2
+            }))
+        ));
+
+        let arr_empty = Array::<f64, _>::zeros(IxDyn(&[0, 3]));
+        assert!(matches!(
+            uniquetol_nd_default(&arr_empty, FlattenAxis::Dim(0)),
+            Err(UniqueTolError::EmptyAxis(0))
+        ));
+
+        let result = uniquetol_nd_default(&arr_empty, FlattenAxis::Dim(1)).unwrap();
+        assert_eq!(result.arr_unique.shape(), [0, 1]);
+        assert_eq!(result.counts_unique, vec![3]);
+
+        let result = uniquetol_nd_default(&arr_empty, FlattenAxis::None).unwrap();
+        assert_eq!(result.get_len_unique(), 0);
+    }
+
+    #[test]
+    fn test_uniquetol_nd_layouts() {
+        let arr = arr_2d();
+        let vals: Vec<f64> = arr.iter().copied().collect();
+        let arrs = [
+            arr.t().to_owned(),
+            arr.clone().slice_move(s![..;2, ..]),
+            Array::from_shape_vec(SHAPE_2D.f(), vals).unwrap(),
+        ];
+
+        for arr in arrs {
+            let arr = arr.into_dyn();
+            assert!(arr.as_slice().is_none());
+
+            let result = uniquetol_nd_default(&arr, FlattenAxis::None).unwrap();
+            let result_standard =
+                uniquetol_nd_default(&arr.as_standard_layout().into_owned(), FlattenAxis::None)
+                    .unwrap();
+            assert_eq!(result, result_standard);
+            assert_eq!(result.remap_to_original(), arr);
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_nd_views() {
+        let arr = arr_3d();
+        let tols = Tols::new(1e-5, 0.0).unwrap();
+        let views = [
+            arr.view(),
+            arr.slice(s![.., ..;2, ..]),
+            arr.slice(s![..;-1, .., 1..]),
+        ];
+
+        for view in views {
+            let arr_owned = view.as_standard_layout().into_owned().into_dyn();
+
+            for axis in 0..3 {
+                let result = uniquetol_nd(
+                    &view,
+                    tols,
+                    NanComparison::default(),
+                    Occurrence::Highest,
+                    ClusterStrategy::default(),
+                    Representative::Mean,
+                    OutputOrder::Stable,
+                    FlattenAxis::Dim(axis),
+                )
+                .unwrap();
+                let result_owned = uniquetol_nd(
+                    &arr_owned,
+                    tols,
+                    NanComparison::default(),
+                    Occurrence::Highest,
+                    ClusterStrategy::default(),
+                    Representative::Mean,
+                    OutputOrder::Stable,
+                    FlattenAxis::Dim(axis),
+                )
+                .unwrap();
+                assert_eq!(result, result_owned);
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_3d_dims_reshape() {
+        let arr = arr_3d();
+        let arr_rows = arr.to_shape((8, 5)).unwrap().into_owned();
+        let tols = Tols::new(1e-5, 0.0).unwrap();
+
+        for representative in [Representative::Member, Representative::Mean] {
+            let result = uniquetol_nd(
+                &arr,
+                tols,
+                NanComparison::default(),
+                Occurrence::Highest,
+                ClusterStrategy::default(),
+                representative,
+                OutputOrder::Stable,
+                FlattenAxis::Dims(vec![0, 1]),
+            )
+            .unwrap();
+            let result_rows = uniquetol_nd(
+                &arr_rows,
+                tols,
+                NanComparison::default(),
+                Occurrence::Highest,
+                ClusterStrategy::default(),
+                representative,
+                OutputOrder::Stable,
+                FlattenAxis::Dim(0),
+            )
+            .unwrap();
+
+            assert_eq!(result.arr_unique, result_rows.arr_unique);
+            assert_eq!(result.indices_unique, result_rows.indices_unique);
+            assert_eq!(result.inverse_unique, result_rows.inverse_unique);
+            assert_eq!(result.counts_unique, result_rows.counts_unique);
+            assert_eq!(result.shape_original, vec![2, 4, 5]);
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_4d_dims() {
+        // Four (time, channel) pairs holding two distinct 2x2 images
+        let img_a = [1.0, 2.0, 3.0, 4.0];
+        let img_b = [5.0, 6.0, 7.0, 8.0];
+        let vals: Vec<f64> = [img_a, img_b, img_b, img_a].concat();
+        let arr = Array::from_shape_vec(IxDyn(&[2, 2, 2, 2]), vals).unwrap();
+
+        let result = uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![0, 1])).unwrap();
+        assert_eq!(result.arr_unique.shape(), [2, 2, 2]);
+        assert_eq!(result.indices_unique, vec![0, 1]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 1, 0]);
+        assert_eq!(result.counts_unique, vec![2, 2]);
+        assert_eq!(result.remap_to_original(), arr);
+
+        // Listing the axes in reverse order enumerates the rows channel-first
+        let result = uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![1, 0])).unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 1, 1, 0]);
+        assert_eq!(result.remap_to_original(), arr);
+
+        // The collapsed axis sits where the lowest of the combined axes was
+        let result = uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![3, 1])).unwrap();
+        assert_eq!(result.arr_unique.shape(), [2, 4, 2]);
+        assert_eq!(result.get_len_original(), 4);
+        assert_eq!(result.remap_to_original(), arr);
+
+        for axis in 0..4 {
+            let result = uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![axis])).unwrap();
+            let result_axis = uniquetol_nd_default(&arr, FlattenAxis::Dim(axis)).unwrap();
+            assert_eq!(result.arr_unique, result_axis.arr_unique);
+            assert_eq!(result.inverse_unique, result_axis.inverse_unique);
+        }
+
+        assert!(matches!(
+            uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![0, 4])),
+            Err(UniqueTolError::AxisBounds(AxisBoundsError {
+                axis: 4,
+                ndim: 4
+            }))
+        ));
+        assert!(matches!(
+            uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![1, 2, 1])),
+            Err(UniqueTolError::DuplicateAxis(1))
+        ));
+        assert!(matches!(
+            uniquetol_nd_default(&arr, FlattenAxis::Dims(vec![])),
+            Err(UniqueTolError::NoAxes)
+        ));
+    }
+
+    #[test]
+    fn test_uniquetol_nd_columnwise() {
+        // Positions in meters and temperatures in Kelvin
+        let arr = array![[1.0, 300.0], [1.0005, 300.4], [1.0, 301.0], [1.01, 300.0]];
+        let tols = array![Tols::new(1e-3, 0.0).unwrap(), Tols::new(0.5, 0.0).unwrap()];
+
+        for strategy in [ClusterStrategy::Anchor, ClusterStrategy::Joint] {
+            let result = uniquetol_nd_columnwise(
+                &arr,
+                &tols,
+                NanComparison::default(),
+                Occurrence::default(),
+                strategy,
+                Representative::default(),
+                OutputOrder::Stable,
+                FlattenAxis::Dim(0),
+            )
+            .unwrap();
+            assert_eq!(result.inverse_unique, vec![0, 0, 1, 2]);
+            assert_eq!(result.indices_unique, vec![0, 2, 3]);
+        }
+
+        // Each slice along axis 0 of a 3-D array is a 2-D grid of positions
+        let arr_3d = Array3::from_shape_fn((4, 2, 2), |(i, j, _)| arr[[i, j]]);
+        let tols_2d = array![
+            [Tols::new(1e-3, 0.0).unwrap(), Tols::new(1e-3, 0.0).unwrap()],
+            [Tols::new(0.5, 0.0).unwrap(), Tols::new(0.5, 0.0).unwrap()],
+        ];
+        let result = uniquetol_nd_columnwise(
+            &arr_3d,
+            &tols_2d,
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::Stable,
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 2]);
+
+        let arr_rows = arr.clone().into_shape_with_order((2, 2, 2)).unwrap();
+        let result = uniquetol_nd_columnwise(
+            &arr_rows,
+            &tols,
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::Joint,
+            Representative::default(),
+            OutputOrder::Stable,
+            FlattenAxis::Dims(vec![0, 1]),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 2]);
+
+        let tols_0d = arr0(Tols::new(0.5, 0.0).unwrap());
+        let result = uniquetol_nd_columnwise(
+            &arr,
+            &tols_0d,
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::None,
+        )
+        .unwrap();
+        let result_uniform = uniquetol_nd(
+            &arr,
+            Tols::new(0.5, 0.0).unwrap(),
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::None,
+        )
+        .unwrap();
+        assert_eq!(result, result_uniform);
+    }
+
+    #[test]
+    fn test_uniquetol_nd_columnwise_errors() {
+        let arr = Array::<f64, _>::zeros((4, 3, 2));
+        let uniquetol_columnwise = |tols: &Array<Tols<f64>, IxDyn>, flatten_axis| {
+            uniquetol_nd_columnwise(
+                &arr,
+                tols,
+                NanComparison::default(),
+                Occurrence::default(),
+                ClusterStrategy::default(),
+                Representative::default(),
+                OutputOrder::default(),
+                flatten_axis,
+            )
+        };
+
+        let tols = Array::from_elem(IxDyn(&[3, 2]), Tols::default());
+        assert!(uniquetol_columnwise(&tols, FlattenAxis::Dim(0)).is_ok());
+        assert!(matches!(
+            uniquetol_columnwise(&tols, FlattenAxis::Dim(1)),
+            Err(UniqueTolError::TolsShape(expected, found))
+                if expected == vec![4, 2] && found == vec![3, 2]
+        ));
+        assert!(matches!(
+            uniquetol_columnwise(&tols, FlattenAxis::None),
+            Err(UniqueTolError::TolsShape(_, _))
+        ));
+
+        let tols = Array::from_elem(IxDyn(&[3]), Tols::default());
+        assert!(uniquetol_columnwise(&tols, FlattenAxis::Dims(vec![2, 0])).is_ok());
+
+        let mut tols = Array::from_elem(IxDyn(&[3, 2]), Tols::default());
+        tols[[2, 1]].atol = -1.0;
+        assert!(matches!(
+            uniquetol_columnwise(&tols, FlattenAxis::Dim(0)),
+            Err(UniqueTolError::Tols(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_flatten_axis_serde() {
+        for flatten_axis in [
+            FlattenAxis::None,
+            FlattenAxis::Dim(2),
+            FlattenAxis::Dims(vec![0, 2]),
+        ] {
+            let json = serde_json::to_string(&flatten_axis).unwrap();
+            assert_eq!(
+                serde_json::from_str::<FlattenAxis>(&json).unwrap(),
+                flatten_axis
+            );
+        }
     }
 }
@@ -0,0 +1,922 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use ndarray::{ArrayBase, ArrayView, Data, Dimension, IxDyn, Zip, aview1};
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::isapprox::{ApproxEq, NanComparison, ToleranceRule, Tols};
+use crate::uniquetol_1d::{
+    ClusterStrategy, Occurrence, OutputOrder, Representative, UniqueTolResult, uniquetol_1d,
+};
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{
+    FlattenAxis, UniqueTolNDResult, check_flatten_axis, uniquetol_nd_columnwise,
+};
+use crate::uniquetol_scale::{DataScale, slice_scales, tols_per_position};
+
+#[cfg(feature = "complex")]
+use crate::uniquetol_complex::{uniquetol_1d_complex, uniquetol_nd_complex_columnwise};
+#[cfg(feature = "rayon")]
+use crate::uniquetol_par::{uniquetol_1d_par, uniquetol_nd_columnwise_par};
+
+// Fields left out of serialized options take their default values. `tols` may be any metric
+// implementing `UniqueTolMetric`, set with `metric`, in place of the default `Tols`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        default,
+        bound(deserialize = "F: serde::Deserialize<'de>, T: serde::Deserialize<'de> + Default")
+    )
+)]
+pub struct UniqueTolOptions<F, T = Tols<F>>
+where
+    F: Float + Display + Debug,
+{
+    pub tols: T,
+    pub data_scale: DataScale<F>,
+    pub nan_cmp: NanComparison,
+    pub occurrence: Occurrence,
+    pub strategy: ClusterStrategy,
+    pub representative: Representative,
+    pub order: OutputOrder,
+    pub flatten_axis: FlattenAxis,
+    // Runs real inputs through the rayon-based functions when the `rayon` feature is enabled, and
+    // is ignored otherwise so that options mean the same thing under every feature set. Complex
+    // inputs have no parallel path.
+    pub parallel: bool,
+}
+
+pub type UniqueTol<F, T = Tols<F>> = UniqueTolOptions<F, T>;
+
+impl<F, T> Default for UniqueTolOptions<F, T>
+where
+    F: Float + Display + Debug,
+    T: Default,
+{
+    fn default() -> Self {
+        Self {
+            tols: T::default(),
+            data_scale: DataScale::default(),
+            nan_cmp: NanComparison::default(),
+            occurrence: Occurrence::default(),
+            strategy: ClusterStrategy::default(),
+            representative: Representative::default(),
+            order: OutputOrder::default(),
+            flatten_axis: FlattenAxis::default(),
+            parallel: false,
+        }
+    }
+}
+
+impl<F> UniqueTolOptions<F>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn atol(self, atol: F) -> Self {
+        let tols = Tols { atol, ..self.tols };
+        self.tols(tols)
+    }
+
+    #[inline]
+    pub fn rtol(self, rtol: F) -> Self {
+        let tols = Tols { rtol, ..self.tols };
+        self.tols(tols)
+    }
+
+    #[inline]
+    pub fn max_ulps(self, max_ulps: u64) -> Self {
+        let tols = self.tols.with_max_ulps(max_ulps);
+        self.tols(tols)
+    }
+
+    #[inline]
+    pub fn rule(self, rule: ToleranceRule) -> Self {
+        let tols = self.tols.with_rule(rule);
+        self.tols(tols)
+    }
+}
+
+impl<F, T> UniqueTolOptions<F, T>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    pub fn tols(self, tols: T) -> Self {
+        UniqueTolOptions { tols, ..self }
+    }
+
+    // Swaps the comparison for a custom one, keeping every other option
+    pub fn metric<U>(self, metric: U) -> UniqueTolOptions<F, U> {
+        UniqueTolOptions {
+            tols: metric,
+            data_scale: self.data_scale,
+            nan_cmp: self.nan_cmp,
+            occurrence: self.occurrence,
+            strategy: self.strategy,
+            representative: self.representative,
+            order: self.order,
+            flatten_axis: self.flatten_axis,
+            parallel: self.parallel,
+        }
+    }
+
+    #[inline]
+    pub fn data_scale(self, data_scale: DataScale<F>) -> Self {
+        UniqueTolOptions { data_scale, ..self }
+    }
+
+    #[inline]
+    pub fn nan_cmp(self, nan_cmp: NanComparison) -> Self {
+        UniqueTolOptions { nan_cmp, ..self }
+    }
+
+    #[inline]
+    pub fn nan_equal(self, nan_equal: bool) -> Self {
+        self.nan_cmp(nan_equal.into())
+    }
+
+    #[inline]
+    pub fn occurrence(self, occurrence: Occurrence) -> Self {
+        UniqueTolOptions { occurrence, ..self }
+    }
+
+    #[inline]
+    pub fn strategy(self, strategy: ClusterStrategy) -> Self {
+        UniqueTolOptions { strategy, ..self }
+    }
+
+    #[inline]
+    pub fn representative(self, representative: Representative) -> Self {
+        UniqueTolOptions {
+            representative,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn order(self, order: OutputOrder) -> Self {
+        UniqueTolOptions { order, ..self }
+    }
+
+    #[inline]
+    pub fn flatten_axis(self, flatten_axis: FlattenAxis) -> Self {
+        UniqueTolOptions {
+            flatten_axis,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn axis(self, axis: usize) -> Self {
+        self.flatten_axis(FlattenAxis::Dim(axis))
+    }
+
+    #[inline]
+    pub fn axes(self, axes: Vec<usize>) -> Self {
+        self.flatten_axis(FlattenAxis::Dims(axes))
+    }
+
+    #[inline]
+    pub fn parallel(self, parallel: bool) -> Self {
+        UniqueTolOptions { parallel, ..self }
+    }
+
+    // Checks everything that does not depend on the input; axes are checked against its shape
+    // once `run` is called
+    pub fn validate(&self) -> Result<(), UniqueTolError<F>>
+    where
+        T: UniqueTolMetric<F>,
+    {
+        self.tols.validate()?;
+        self.data_scale.validate()
+    }
+
+    #[inline]
+    pub fn run<A>(&self, arr: A) -> Result<A::Output, UniqueTolError<F>>
+    where
+        A: UniqueTolInput<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        self.validate()?;
+        arr.uniquetol_with(self)
+    }
+
+    // Deduplicates whole records by a floating-point key, borrowing the kept records rather than
+    // copying them. Summarizing is left to the caller since records generally have no mean, so
+    // `representative` has no effect.
+    pub fn run_by_key<'a, R, K>(
+        &self,
+        items: &'a [R],
+        key: K,
+    ) -> Result<UniqueTolResult<&'a R>, UniqueTolError<F>>
+    where
+        F: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+        K: Fn(&R) -> F,
+    {
+        let keys: Vec<F> = items.iter().map(key).collect();
+        let options = self.clone().representative(Representative::Member);
+        Ok(options.run(&keys)?.borrow_from(items))
+    }
+}
+
+// Comparisons that `UniqueTolOptions` checks up front and rescales by its data scale. A custom
+// `ApproxEq` need only say how a scale applies to it; it is taken as valid unless it also
+// overrides `validate`.
+pub trait UniqueTolMetric<F>: Clone
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn validate(&self) -> Result<(), UniqueTolError<F>> {
+        Ok(())
+    }
+
+    // The metric for values measured relative to `scale` rather than to themselves
+    fn scaled(&self, scale: F) -> Self;
+}
+
+impl<F> UniqueTolMetric<F> for Tols<F>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn validate(&self) -> Result<(), UniqueTolError<F>> {
+        Ok(Tols::validate(self)?)
+    }
+
+    #[inline]
+    fn scaled(&self, scale: F) -> Self {
+        Tols::scaled(*self, scale)
+    }
+}
+
+// Element types that `UniqueTolOptions::run` deduplicates with the metric `T`. Each compares
+// values in its own way and supplies the magnitude that `DataScale::Auto` looks at.
+pub trait UniqueTolElement<F, T = Tols<F>>: Copy
+where
+    F: Float + Display + Debug,
+{
+    fn magnitude(self) -> F;
+
+    fn uniquetol_slice(
+        arr: &[Self],
+        tols: T,
+        options: &UniqueTolOptions<F, T>,
+    ) -> UniqueTolResult<Self>;
+
+    // `tols` holds one metric per slice position along `options.flatten_axis`
+    fn uniquetol_array(
+        arr: &ArrayView<Self, IxDyn>,
+        tols: &ArrayView<T, IxDyn>,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<Self>, UniqueTolError<F>>;
+}
+
+// Real values work with any metric, while complex values are always compared by `Tols`
+impl<F, T> UniqueTolElement<F, T> for F
+where
+    F: Float + Display + Debug + Send + Sync,
+    T: ApproxEq<F> + UniqueTolMetric<F> + Sync,
+{
+    #[inline]
+    fn magnitude(self) -> F {
+        self.abs()
+    }
+
+    fn uniquetol_slice(arr: &[F], tols: T, options: &UniqueTolOptions<F, T>) -> UniqueTolResult<F> {
+        #[cfg(feature = "rayon")]
+        if options.parallel {
+            return uniquetol_1d_par(
+                arr,
+                tols,
+                options.nan_cmp,
+                options.occurrence,
+                options.strategy,
+                options.representative,
+                options.order,
+            );
+        }
+
+        uniquetol_1d(
+            arr,
+            tols,
+            options.nan_cmp,
+            options.occurrence,
+            options.strategy,
+            options.representative,
+            options.order,
+        )
+    }
+
+    fn uniquetol_array(
+        arr: &ArrayView<F, IxDyn>,
+        tols: &ArrayView<T, IxDyn>,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<F>, UniqueTolError<F>> {
+        #[cfg(feature = "rayon")]
+        if options.parallel {
+            return uniquetol_nd_columnwise_par(
+                arr,
+                tols,
+                options.nan_cmp,
+                options.occurrence,
+                options.strategy,
+                options.representative,
+                options.order,
+                options.flatten_axis.clone(),
+            );
+        }
+
+        uniquetol_nd_columnwise(
+            arr,
+            tols,
+            options.nan_cmp,
+            options.occurrence,
+            options.strategy,
+            options.representative,
+            options.order,
+            options.flatten_axis.clone(),
+        )
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<F> UniqueTolElement<F> for Complex<F>
+where
+    F: Float + Display + Debug,
+{
+    #[inline]
+    fn magnitude(self) -> F {
+        self.norm()
+    }
+
+    fn uniquetol_slice(
+        arr: &[Complex<F>],
+        tols: Tols<F>,
+        options: &UniqueTolOptions<F>,
+    ) -> UniqueTolResult<Complex<F>> {
+        uniquetol_1d_complex(
+            arr,
+            tols,
+            options.nan_cmp,
+            options.occurrence,
+            options.strategy,
+            options.representative,
+            options.order,
+        )
+    }
+
+    fn uniquetol_array(
+        arr: &ArrayView<Complex<F>, IxDyn>,
+        tols: &ArrayView<Tols<F>, IxDyn>,
+        options: &UniqueTolOptions<F>,
+    ) -> Result<UniqueTolNDResult<Complex<F>>, UniqueTolError<F>> {
+        uniquetol_nd_complex_columnwise(
+            arr,
+            tols,
+            options.nan_cmp,
+            options.occurrence,
+            options.strategy,
+            options.representative,
+            options.order,
+            options.flatten_axis.clone(),
+        )
+    }
+}
+
+// Inputs accepted by `UniqueTolOptions::run`. Slices and vectors give a `UniqueTolResult`,
+// while arrays of any dimensionality give a `UniqueTolNDResult`.
+pub trait UniqueTolInput<F, T = Tols<F>>
+where
+    F: Float + Display + Debug,
+{
+    type Output;
+
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<Self::Output, UniqueTolError<F>>;
+}
+
+impl<E, F, T> UniqueTolInput<F, T> for &[E]
+where
+    E: UniqueTolElement<F, T>,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+{
+    type Output = UniqueTolResult<E>;
+
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<E>, UniqueTolError<F>> {
+        // A slice has the single axis 0, along which flattening changes nothing, so its values
+        // all share one position
+        let arr = aview1(self).into_dyn();
+        check_flatten_axis(&arr, &options.flatten_axis)?;
+
+        let tols = tols_per_position(
+            &arr,
+            &options.tols,
+            &options.data_scale,
+            &FlattenAxis::None,
+            E::magnitude,
+        )?;
+
+        Ok(E::uniquetol_slice(self, tols[[]].clone(), options))
+    }
+}
+
+impl<E, F, T> UniqueTolInput<F, T> for &Vec<E>
+where
+    E: UniqueTolElement<F, T>,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+{
+    type Output = UniqueTolResult<E>;
+
+    #[inline]
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<E>, UniqueTolError<F>> {
+        self.as_slice().uniquetol_with(options)
+    }
+}
+
+impl<E, F, T, const N: usize> UniqueTolInput<F, T> for &[E; N]
+where
+    E: UniqueTolElement<F, T>,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+{
+    type Output = UniqueTolResult<E>;
+
+    #[inline]
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<E>, UniqueTolError<F>> {
+        self.as_slice().uniquetol_with(options)
+    }
+}
+
+impl<E, F, T, S, D> UniqueTolInput<F, T> for &ArrayBase<S, D>
+where
+    E: UniqueTolElement<F, T>,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+    S: Data<Elem = E>,
+    D: Dimension,
+{
+    type Output = UniqueTolNDResult<E>;
+
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<E>, UniqueTolError<F>> {
+        let arr = self.view().into_dyn();
+        let tols = tols_per_position(
+            &arr,
+            &options.tols,
+            &options.data_scale,
+            &options.flatten_axis,
+            E::magnitude,
+        )?;
+
+        E::uniquetol_array(&arr, &tols.view(), options)
+    }
+}
+
+// An array paired with one metric per slice position, shaped like a single slice, which take
+// the place of `options.tols`. Any data scale applies to each of them in turn.
+impl<E, F, T, S, D, St, Dt> UniqueTolInput<F, T> for (&ArrayBase<S, D>, &ArrayBase<St, Dt>)
+where
+    E: UniqueTolElement<F, T>,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+    S: Data<Elem = E>,
+    D: Dimension,
+    St: Data<Elem = T>,
+    Dt: Dimension,
+{
+    type Output = UniqueTolNDResult<E>;
+
+    fn uniquetol_with(
+        self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<E>, UniqueTolError<F>> {
+        let arr = self.0.view().into_dyn();
+        let tols = self.1.view().into_dyn();
+
+        match slice_scales(
+            &arr,
+            &options.data_scale,
+            &options.flatten_axis,
+            E::magnitude,
+        )? {
+            None => E::uniquetol_array(&arr, &tols, options),
+            Some(scales) if scales.shape() == tols.shape() => {
+                // Scaling would otherwise mask an invalid `rtol`
+                tols.iter().try_for_each(|tols| tols.validate())?;
+                let tols = Zip::from(&tols)
+                    .and(&scales)
+                    .map_collect(|tols, &scale| tols.scaled(scale));
+                E::uniquetol_array(&arr, &tols.view(), options)
+            }
+            Some(scales) => Err(UniqueTolError::TolsShape(
+                scales.shape().to_vec(),
+                tols.shape().to_vec(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uniquetol_1d::uniquetol_1d;
+    use crate::uniquetol_nd::{uniquetol_nd, uniquetol_nd_columnwise};
+    use alloc::vec;
+    use ndarray::array;
+
+    #[test]
+    fn test_uniquetol_options_1d() {
+        let arr = vec![1.0, 1.0 + 1e-7, 2.0, 2.0 - 1e-7];
+
+        let result = UniqueTol::new()
+            .atol(1e-6)
+            .rtol(0.0)
+            .nan_equal(true)
+            .occurrence(Occurrence::Highest)
+            .run(&arr)
+            .unwrap();
+        let expected = uniquetol_1d(
+            &arr,
+            Tols::new(1e-6, 0.0).unwrap(),
+            NanComparison::Equal,
+            Occurrence::Highest,
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+        );
+        assert_eq!(result, expected);
+        assert_eq!(result.indices_unique, vec![2, 1]);
+
+        let arr_nan = [f64::NAN, f64::NAN];
+        let result = UniqueTol::new().nan_equal(true).run(&arr_nan[..]).unwrap();
+        assert_eq!(result.get_len_unique(), 1);
+        let result = UniqueTol::new().nan_equal(false).run(&arr_nan[..]).unwrap();
+        assert_eq!(result.get_len_unique(), 2);
+
+        let result = UniqueTol::new().axis(0).run(&[3.0, 1.0, 3.0]).unwrap();
+        assert_eq!(result.arr_unique, vec![1.0, 3.0]);
+
+        let result = UniqueTol::new().axis(1).run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::AxisBounds(_))));
+    }
+
+    #[test]
+    fn test_uniquetol_options_nd() {
+        let arr = array![[1.0, 2.0], [3.0, 4.0], [1.0, 2.0 + 1e-9]];
+
+        let result = UniqueTolOptions::new()
+            .axis(0)
+            .order(OutputOrder::Stable)
+            .run(&arr)
+            .unwrap();
+        let expected = uniquetol_nd(
+            &arr,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::Stable,
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(result.indices_unique, vec![0, 1]);
+
+        let result = UniqueTol::new()
+            .tols(Tols::new(0.0, 0.6).unwrap())
+            .data_scale(DataScale::Auto)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.arr_unique, array![1.0, 4.0].into_dyn());
+
+        let result = UniqueTol::new().axes(vec![1, 1]).run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::DuplicateAxis(1))));
+    }
+
+    #[test]
+    fn test_uniquetol_options_validate() {
+        let options = UniqueTol::new().atol(-1.0);
+        assert!(matches!(options.validate(), Err(UniqueTolError::Tols(_))));
+        assert!(options.run(&[1.0, 2.0]).is_err());
+
+        let options = UniqueTol::new().data_scale(DataScale::Value(f64::INFINITY));
+        assert!(matches!(
+            options.validate(),
+            Err(UniqueTolError::DataScale(_))
+        ));
+        assert!(options.run(&array![1.0, 2.0]).is_err());
+
+        assert!(UniqueTol::<f32>::new().validate().is_ok());
+    }
+
+    #[test]
+    fn test_uniquetol_options_by_key() {
+        let items = [(440.0, "a"), (220.0, "b"), (440.0005, "c"), (880.0, "d")];
+
+        let options = UniqueTol::new()
+            .atol(1e-3)
+            .rtol(0.0)
+            .representative(Representative::Mean)
+            .order(OutputOrder::Stable);
+        let result = options.run_by_key(&items, |item| item.0).unwrap();
+        assert_eq!(result.arr_unique, vec![&items[0], &items[1], &items[3]]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0, 2]);
+
+        // Scaled by the largest key, the tolerance grows to 308 and both 440s join 220
+        let result = UniqueTol::new()
+            .tols(Tols::new(0.0, 0.35).unwrap())
+            .data_scale(DataScale::Auto)
+            .run_by_key(&items, |item| item.0)
+            .unwrap();
+        assert_eq!(result.indices_unique, vec![1, 3]);
+
+        let result = UniqueTol::new()
+            .atol(-1.0)
+            .run_by_key(&items, |item| item.0);
+        assert!(matches!(result, Err(UniqueTolError::Tols(_))));
+    }
+
+    #[test]
+    fn test_uniquetol_options_by_key_records() {
+        #[derive(Debug, PartialEq)]
+        struct Peak {
+            freq: f64,
+            label: &'static str,
+        }
+
+        let peaks = [
+            Peak {
+                freq: 440.0,
+                label: "a",
+            },
+            Peak {
+                freq: 220.0,
+                label: "b",
+            },
+            Peak {
+                freq: 440.0005,
+                label: "c",
+            },
+            Peak {
+                freq: 880.0,
+                label: "d",
+            },
+            Peak {
+                freq: 219.9998,
+                label: "e",
+            },
+        ];
+        let options = UniqueTol::new().atol(1e-3).rtol(0.0);
+
+        let result = options
+            .clone()
+            .order(OutputOrder::Stable)
+            .run_by_key(&peaks, |peak| peak.freq)
+            .unwrap();
+        assert_eq!(result.indices_unique, vec![0, 4, 3]);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0, 2, 1]);
+        assert_eq!(result.counts_unique, vec![2, 2, 1]);
+        let labels: Vec<_> = result.arr_unique.iter().map(|peak| peak.label).collect();
+        assert_eq!(labels, vec!["a", "e", "d"]);
+        assert_eq!(result.remap_to_original()[2], &peaks[0]);
+
+        let result = options.run_by_key(&peaks, |peak| peak.freq).unwrap();
+        let freqs: Vec<_> = result.arr_unique.iter().map(|peak| peak.freq).collect();
+        assert_eq!(freqs, vec![219.9998, 440.0, 880.0]);
+    }
+
+    #[test]
+    fn test_uniquetol_options_columnwise() {
+        let arr = array![[1.0, 300.0], [1.04, 330.0], [2.0, 300.0]];
+        let tols = array![Tols::new(0.1, 0.0).unwrap(), Tols::new(50.0, 0.0).unwrap()];
+
+        let result = UniqueTol::new().axis(0).run((&arr, &tols)).unwrap();
+        let expected = uniquetol_nd_columnwise(
+            &arr,
+            &tols,
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(result.inverse_unique, vec![0, 0, 1]);
+
+        // The data scale applies to every per-position tolerance
+        let tols_rel = Tols::new(0.0, 0.1).unwrap();
+        let data_scale = DataScale::Columns(array![1.0, 300.0].into_dyn());
+        let result = UniqueTol::new()
+            .axis(0)
+            .data_scale(data_scale.clone())
+            .run((&arr, &array![tols_rel, tols_rel]))
+            .unwrap();
+        let expected = UniqueTol::new()
+            .tols(tols_rel)
+            .axis(0)
+            .data_scale(data_scale)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result, expected);
+
+        let result = UniqueTol::new().axis(0).run((&arr, &array![tols_rel]));
+        assert!(matches!(result, Err(UniqueTolError::TolsShape(_, _))));
+
+        let result = UniqueTol::new()
+            .axis(0)
+            .data_scale(DataScale::Columns(array![1.0].into_dyn()))
+            .run((&arr, &tols));
+        assert!(matches!(result, Err(UniqueTolError::TolsShape(_, _))));
+    }
+
+    // Joins values no more than a fixed gap apart, growing with the data scale
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Gap(f64);
+
+    impl ApproxEq<f64> for Gap {
+        fn isapprox(&self, x: f64, y: f64, _nan_cmp: NanComparison) -> bool {
+            (x - y).abs() <= self.0
+        }
+    }
+
+    impl UniqueTolMetric<f64> for Gap {
+        fn validate(&self) -> Result<(), UniqueTolError<f64>> {
+            match self.0 >= 0.0 {
+                true => Ok(()),
+                false => Err(UniqueTolError::DataScale(self.0)),
+            }
+        }
+
+        fn scaled(&self, scale: f64) -> Self {
+            Gap(self.0 * scale)
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_options_metric() {
+        use crate::uniquetol_traits::{UniqueTol1D, UniqueTolND};
+
+        let arr = [1.0, 1.4, 2.0, 5.0];
+        let options = UniqueTol::new().order(OutputOrder::Stable).metric(Gap(0.5));
+
+        let result = options.run(&arr).unwrap();
+        assert_eq!(result.arr_unique, vec![1.0, 2.0, 5.0]);
+        assert_eq!(arr.uniquetol(&options), result);
+
+        let result = options.clone().data_scale(DataScale::Value(4.0)).run(&arr);
+        assert_eq!(result.unwrap().arr_unique, vec![1.0, 5.0]);
+
+        let arr_2d = array![[1.0, 10.0], [1.4, 12.0], [3.0, 10.5]];
+        let result = (&arr_2d).uniquetol_axis(&options, 0);
+        assert_eq!(result.inverse_unique, vec![0, 1, 2]);
+
+        let gaps = array![Gap(0.5), Gap(2.5)];
+        let result = options.clone().axis(0).run((&arr_2d, &gaps)).unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 1]);
+
+        let result = options.clone().metric(Gap(-1.0)).run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::DataScale(_))));
+
+        let result = options.parallel(true).run(&arr).unwrap();
+        assert_eq!(result.arr_unique, vec![1.0, 2.0, 5.0]);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_uniquetol_options_complex() {
+        use crate::uniquetol_complex::uniquetol_1d_complex;
+        use num_complex::Complex;
+
+        let arr = [
+            Complex::new(1.0, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(1.0, 1e-9),
+        ];
+        let result = UniqueTol::new()
+            .order(OutputOrder::Stable)
+            .run(&arr)
+            .unwrap();
+        let expected = uniquetol_1d_complex(
+            arr,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::Stable,
+        );
+        assert_eq!(result, expected);
+        assert_eq!(result.inverse_unique, vec![0, 1, 0]);
+
+        // Scaled by each column's largest modulus, the small column keeps its two values apart
+        let arr = array![
+            [Complex::new(1.0, 0.0), Complex::new(0.0, 1000.0)],
+            [Complex::new(1.5, 0.0), Complex::new(0.0, 1000.0)]
+        ];
+        let options = UniqueTol::new().tols(Tols::new(0.0, 1e-3).unwrap()).axis(0);
+
+        let result = options
+            .clone()
+            .data_scale(DataScale::Auto)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.get_len_unique(), 2);
+
+        let result = options
+            .data_scale(DataScale::Value(1000.0))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.get_len_unique(), 1);
+    }
+
+    // Without the `rayon` feature, `parallel` is ignored and both runs take the same path
+    #[test]
+    fn test_uniquetol_options_parallel() {
+        use crate::uniquetol_1d::test_arr::TEST_ARR;
+        use ndarray::{Array, IxDyn};
+
+        let arr = Array::from_shape_vec(IxDyn(&[9, 9, 9]), TEST_ARR.to_vec()).unwrap();
+
+        for options in [
+            UniqueTol::new(),
+            UniqueTol::new().axis(0).strategy(ClusterStrategy::Complete),
+            UniqueTol::new().axes(vec![0, 2]).order(OutputOrder::Stable),
+            UniqueTol::new()
+                .tols(Tols::new(0.0, 0.1).unwrap())
+                .data_scale(DataScale::Auto)
+                .axis(1),
+        ] {
+            let result = options.clone().parallel(true).run(&arr).unwrap();
+            assert_eq!(result, options.run(&arr).unwrap());
+
+            let options = options.flatten_axis(FlattenAxis::None);
+            let result = options.clone().parallel(true).run(&TEST_ARR).unwrap();
+            assert_eq!(result, options.run(&TEST_ARR).unwrap());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_uniquetol_options_serde() {
+        let options = UniqueTol::new()
+            .tols(Tols::new(1e-6, 1e-3).unwrap())
+            .data_scale(DataScale::Columns(array![1.0, 10.0].into_dyn()))
+            .occurrence(Occurrence::Highest)
+            .axes(vec![0, 2]);
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            serde_json::from_str::<UniqueTolOptions<f64>>(&json).unwrap(),
+            options
+        );
+
+        let options = serde_json::from_str::<UniqueTolOptions<f64>>(
+            r#"{"strategy": "Complete", "flatten_axis": {"Dim": 1}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            options,
+            UniqueTol::new().strategy(ClusterStrategy::Complete).axis(1)
+        );
+
+        let result = serde_json::from_str::<UniqueTolOptions<f64>>(
+            r#"{"tols": {"atol": -1.0, "rtol": 0.0}}"#,
+        );
+        assert!(result.is_err());
+    }
+}
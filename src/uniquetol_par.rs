@@ -0,0 +1,384 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use core::fmt::{Debug, Display};
+use core::ops::Index;
+use ndarray::{ArrayBase, ArrayView, Axis, Data, Dimension, IxDyn};
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::isapprox::{ApproxEq, NanComparison};
+use crate::uniquetol_1d::{
+    ClusterStrategy, Occurrence, OutputOrder, Representative, UniqueTolResult, cluster_starts,
+    cmp_nan_last,
+};
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{
+    FlattenAxis, UniqueTolNDResult, check_flatten_axis, flatten_logical, row_columns, shape_slice,
+    uniquetol_groups, uniquetol_nd_flatten_joint_by,
+};
+use crate::uniquetol_options::UniqueTolMetric;
+
+// Rayon's parallel sort is stable, so this yields the same permutation as `sortperm`
+pub fn sortperm_par<F>(arr: &[F], reverse: bool) -> Vec<usize>
+where
+    F: Float + Display + Debug + Send + Sync,
+{
+    let mut perm: Vec<usize> = (0..arr.len()).collect();
+
+    perm.par_sort_by(|&i, &j| cmp_nan_last(arr[i], arr[j], reverse));
+    perm
+}
+
+pub(crate) fn uniquetol_1d_par<A, F, T>(
+    arr: A,
+    tols: T,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+) -> UniqueTolResult<F>
+where
+    A: AsRef<[F]>,
+    F: Float + Display + Debug + Send + Sync,
+    T: ApproxEq<F>,
+{
+    let arr = arr.as_ref();
+    let n = arr.len();
+
+    let perm_sorted = match occurrence {
+        Occurrence::Lowest => sortperm_par(arr, false),
+        Occurrence::Highest => sortperm_par(arr, true),
+    };
+
+    // The sweep itself is inherently sequential, but everything around it is not
+    let starts = cluster_starts(arr, &perm_sorted, strategy, tols.is_monotone(), |x, y| {
+        tols.isapprox(x, y, nan_cmp)
+    });
+
+    let indices_unique: Vec<usize> = starts.par_iter().map(|&start| perm_sorted[start]).collect();
+    let counts_unique = (0..starts.len())
+        .into_par_iter()
+        .map(|i| starts.get(i + 1).copied().unwrap_or(n) - starts[i])
+        .collect();
+
+    // Each cluster labels its own range of the sorted permutation, and sorting the labelled
+    // indices back into input order then gives the inverse without any shared writes
+    let mut labels: Vec<(usize, usize)> = (0..starts.len())
+        .into_par_iter()
+        .flat_map_iter(|idx_unique| {
+            let end = starts.get(idx_unique + 1).copied().unwrap_or(n);
+            perm_sorted[starts[idx_unique]..end]
+                .iter()
+                .map(move |&idx| (idx, idx_unique))
+        })
+        .collect();
+    labels.par_sort_unstable_by_key(|&(idx, _)| idx);
+    let inverse_unique = labels
+        .into_par_iter()
+        .map(|(_, idx_unique)| idx_unique)
+        .collect();
+
+    let arr_unique = indices_unique.par_iter().map(|&i| arr[i]).collect();
+
+    let result = UniqueTolResult {
+        arr_unique,
+        indices_unique,
+        inverse_unique,
+        counts_unique,
+    };
+
+    result.finish(arr, representative, order, Representative::summarize)
+}
+
+fn refine_columns_par<'a, F, C, I, T, G>(
+    k: usize,
+    columns: I,
+    tols_at: G,
+    nan_cmp: NanComparison,
+    strategy: ClusterStrategy,
+) -> Vec<Vec<usize>>
+where
+    F: Float + Display + Debug + Send + Sync,
+    C: Index<usize, Output = F> + Sync,
+    I: Iterator<Item = C>,
+    T: ApproxEq<F> + Sync + 'a,
+    G: Fn(usize) -> &'a T,
+{
+    let mut groups: Vec<Vec<usize>> = vec![(0..k).collect()];
+
+    for (j, column) in columns.enumerate() {
+        let tols = tols_at(j);
+        groups = groups
+            .par_iter()
+            .flat_map_iter(|group| {
+                let sub_arr: Vec<F> = group.iter().map(|&i| column[i]).collect();
+                uniquetol_groups(group, &sub_arr, tols, nan_cmp, strategy)
+            })
+            .collect();
+    }
+
+    groups
+}
+
+#[allow(clippy::too_many_arguments)]
+fn uniquetol_nd_flatten_axes_par<'a, F, T, G>(
+    arr: &ArrayView<F, IxDyn>,
+    tols_at: G,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> UniqueTolNDResult<F>
+where
+    F: Float + Display + Debug + Send + Sync,
+    T: ApproxEq<F> + Sync + 'a,
+    G: Fn(usize) -> &'a T,
+{
+    let groups = match &flatten_axis {
+        // Joint grouping follows links from one slice to the next, so it runs sequentially
+        _ if strategy == ClusterStrategy::Joint => None,
+        FlattenAxis::Dims(axes) => {
+            let k = axes.iter().map(|&axis| arr.len_of(Axis(axis))).product();
+            Some(refine_columns_par(
+                k,
+                row_columns(arr, axes),
+                &tols_at,
+                nan_cmp,
+                strategy,
+            ))
+        }
+        _ => {
+            let axis = flatten_axis.get_axis_unique();
+            let lanes = arr.lanes(Axis(axis)).into_iter();
+            Some(refine_columns_par(
+                arr.len_of(Axis(axis)),
+                lanes,
+                &tols_at,
+                nan_cmp,
+                strategy,
+            ))
+        }
+    };
+
+    let result = match groups {
+        Some(groups) => UniqueTolNDResult::from_groups(arr, &groups, occurrence, flatten_axis),
+        None => uniquetol_nd_flatten_joint_by(
+            arr,
+            occurrence,
+            &flatten_axis,
+            |pos, x, y| tols_at(pos).isapprox(x, y, nan_cmp),
+            |x, y| cmp_nan_last(x, y, false),
+        ),
+    };
+
+    result.finish(arr, representative, order, Representative::summarize)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn uniquetol_nd_par<F, T, S, D>(
+    arr: &ArrayBase<S, D>,
+    tols: T,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> Result<UniqueTolNDResult<F>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug + Send + Sync,
+    T: ApproxEq<F> + Sync,
+    S: Data<Elem = F>,
+    D: Dimension,
+{
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
+    match flatten_axis {
+        FlattenAxis::None => {
+            let arr_flat = flatten_logical(arr);
+            let result = uniquetol_1d_par(
+                &*arr_flat,
+                tols,
+                nan_cmp,
+                occurrence,
+                strategy,
+                representative,
+                order,
+            );
+            Ok(UniqueTolNDResult::from_flat(result, arr.shape()))
+        }
+        flatten_axis => Ok(uniquetol_nd_flatten_axes_par(
+            arr,
+            |_| &tols,
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            flatten_axis,
+        )),
+    }
+}
+
+// The parallel counterpart of `uniquetol_nd_columnwise`, with one metric per slice position
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn uniquetol_nd_columnwise_par<F, T, S, D, St, Dt>(
+    arr: &ArrayBase<S, D>,
+    tols: &ArrayBase<St, Dt>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    strategy: ClusterStrategy,
+    representative: Representative,
+    order: OutputOrder,
+    flatten_axis: FlattenAxis,
+) -> Result<UniqueTolNDResult<F>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug + Send + Sync,
+    T: ApproxEq<F> + UniqueTolMetric<F> + Sync,
+    S: Data<Elem = F>,
+    D: Dimension,
+    St: Data<Elem = T>,
+    Dt: Dimension,
+{
+    let arr = &arr.view().into_dyn();
+    check_flatten_axis(arr, &flatten_axis)?;
+
+    let shape_expected = shape_slice(arr.shape(), &flatten_axis);
+
+    if tols.shape() != shape_expected.as_slice() {
+        return Err(UniqueTolError::TolsShape(
+            shape_expected,
+            tols.shape().to_vec(),
+        ));
+    }
+
+    let tols_flat = flatten_logical(&tols.view().into_dyn());
+    tols_flat.iter().try_for_each(|tols| tols.validate())?;
+
+    match flatten_axis {
+        FlattenAxis::None => uniquetol_nd_par(
+            arr,
+            tols_flat[0].clone(),
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            FlattenAxis::None,
+        ),
+        flatten_axis => Ok(uniquetol_nd_flatten_axes_par(
+            arr,
+            |j| &tols_flat[j],
+            nan_cmp,
+            occurrence,
+            strategy,
+            representative,
+            order,
+            flatten_axis,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isapprox::Tols;
+    use crate::uniquetol_1d::test_arr::TEST_ARR;
+    use crate::uniquetol_1d::uniquetol_1d;
+    use crate::uniquetol_nd::uniquetol_nd;
+    use ndarray::Array;
+
+    const OCCURRENCES: [Occurrence; 2] = [Occurrence::Lowest, Occurrence::Highest];
+    const STRATEGIES: [ClusterStrategy; 4] = [
+        ClusterStrategy::Anchor,
+        ClusterStrategy::Chaining,
+        ClusterStrategy::Complete,
+        ClusterStrategy::Joint,
+    ];
+
+    #[test]
+    fn test_uniquetol_1d_par() {
+        let tols = Tols::default();
+        let nan_cmp = NanComparison::default();
+        let representative = Representative::Median;
+
+        for occurrence in OCCURRENCES {
+            for strategy in STRATEGIES {
+                for order in [OutputOrder::Sorted, OutputOrder::Stable] {
+                    let result = uniquetol_1d(
+                        TEST_ARR,
+                        tols,
+                        nan_cmp,
+                        occurrence,
+                        strategy,
+                        representative,
+                        order,
+                    );
+                    let result_par = uniquetol_1d_par(
+                        TEST_ARR,
+                        tols,
+                        nan_cmp,
+                        occurrence,
+                        strategy,
+                        representative,
+                        order,
+                    );
+                    assert_eq!(result_par, result);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_nd_par() {
+        let arr = Array::from_shape_vec(IxDyn(&[9, 9, 9]), TEST_ARR.to_vec()).unwrap();
+        let tols = Tols::new(100.0, 0.0).unwrap();
+        let nan_cmp = NanComparison::default();
+        let representative = Representative::Mean;
+        let order = OutputOrder::Stable;
+        let flatten_axes = [
+            FlattenAxis::None,
+            FlattenAxis::Dim(0),
+            FlattenAxis::Dim(1),
+            FlattenAxis::Dim(2),
+            FlattenAxis::Dims(vec![2, 0]),
+        ];
+
+        for occurrence in OCCURRENCES {
+            for strategy in STRATEGIES {
+                for flatten_axis in flatten_axes.iter() {
+                    let result = uniquetol_nd(
+                        &arr,
+                        tols,
+                        nan_cmp,
+                        occurrence,
+                        strategy,
+                        representative,
+                        order,
+                        flatten_axis.clone(),
+                    );
+                    let result_par = uniquetol_nd_par(
+                        &arr,
+                        tols,
+                        nan_cmp,
+                        occurrence,
+                        strategy,
+                        representative,
+                        order,
+                        flatten_axis.clone(),
+                    );
+                    assert_eq!(result_par.unwrap(), result.unwrap());
+                }
+            }
+        }
+    }
+}
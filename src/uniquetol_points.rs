@@ -0,0 +1,639 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use ndarray::{ArrayBase, ArrayView1, ArrayView2, Axis, Data, Ix2};
+use num_traits::Float;
+
+use crate::isapprox::{NanComparison, Tols, isapprox_ulps};
+use crate::uniquetol_1d::{ClusterStrategy, Occurrence, OutputOrder, Representative};
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{FlattenAxis, UniqueTolNDResult, check_flatten_axis};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "NormUnchecked<F>",
+        bound(deserialize = "F: Float + Display + Debug + serde::Deserialize<'de>")
+    )
+)]
+pub enum Norm<F> {
+    Manhattan,
+    #[default]
+    Euclidean,
+    Chebyshev,
+    Minkowski(F),
+}
+
+impl<F> Norm<F>
+where
+    F: Float + Display + Debug,
+{
+    pub(crate) fn validate(self) -> Result<(), UniqueTolError<F>> {
+        match self {
+            Norm::Minkowski(p) if !(p.is_finite() && p >= F::one()) => {
+                Err(UniqueTolError::NormOrder(p))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn norm<I>(self, vals: I) -> F
+    where
+        I: Iterator<Item = F>,
+    {
+        match self {
+            Norm::Manhattan => vals.fold(F::zero(), |acc, x| acc + x.abs()),
+            Norm::Euclidean => vals.fold(F::zero(), |acc, x| acc + x * x).sqrt(),
+            Norm::Chebyshev => vals.fold(F::zero(), |acc, x| acc.max(x.abs())),
+            Norm::Minkowski(p) => vals
+                .fold(F::zero(), |acc, x| acc + x.abs().powf(p))
+                .powf(p.recip()),
+        }
+    }
+}
+
+// Deserialized norms are checked with `Norm::validate`, so a Minkowski order below 1 is rejected
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+enum NormUnchecked<F> {
+    Manhattan,
+    Euclidean,
+    Chebyshev,
+    Minkowski(F),
+}
+
+#[cfg(feature = "serde")]
+impl<F> TryFrom<NormUnchecked<F>> for Norm<F>
+where
+    F: Float + Display + Debug,
+{
+    type Error = UniqueTolError<F>;
+
+    fn try_from(value: NormUnchecked<F>) -> Result<Self, Self::Error> {
+        let norm = match value {
+            NormUnchecked::Manhattan => Norm::Manhattan,
+            NormUnchecked::Euclidean => Norm::Euclidean,
+            NormUnchecked::Chebyshev => Norm::Chebyshev,
+            NormUnchecked::Minkowski(p) => Norm::Minkowski(p),
+        };
+        norm.validate().map(|_| norm)
+    }
+}
+
+// Static k-d tree over a subset of rows, with each subslice of `nodes` split at its median
+// along the axis given by its depth
+pub(crate) struct KdTree<'a, F> {
+    points: ArrayView2<'a, F>,
+    nodes: Vec<usize>,
+}
+
+impl<'a, F> KdTree<'a, F>
+where
+    F: Float + Display + Debug,
+{
+    pub(crate) fn new(points: ArrayView2<'a, F>, mut nodes: Vec<usize>) -> Self {
+        Self::build(&points, &mut nodes, 0);
+        KdTree { points, nodes }
+    }
+
+    fn build(points: &ArrayView2<F>, nodes: &mut [usize], depth: usize) {
+        let dim = points.ncols();
+
+        if nodes.len() <= 1 || dim == 0 {
+            return;
+        }
+
+        let axis = depth % dim;
+        let mid = nodes.len() / 2;
+        nodes.select_nth_unstable_by(mid, |&i, &j| {
+            points[[i, axis]]
+                .partial_cmp(&points[[j, axis]])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let (left, right) = nodes.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    // Collects every row within `radius` of `point` along each axis, which for any p-norm is a
+    // superset of the rows within that distance
+    pub(crate) fn within(&self, point: &ArrayView1<F>, radius: F, found: &mut Vec<usize>) {
+        self.search(&self.nodes, point, radius, 0, found);
+    }
+
+    fn search(
+        &self,
+        nodes: &[usize],
+        point: &ArrayView1<F>,
+        radius: F,
+        depth: usize,
+        found: &mut Vec<usize>,
+    ) {
+        let dim = self.points.ncols();
+
+        if nodes.is_empty() {
+            return;
+        } else if dim == 0 {
+            found.extend_from_slice(nodes);
+            return;
+        }
+
+        let mid = nodes.len() / 2;
+        let idx = nodes[mid];
+        let row = self.points.row(idx);
+
+        if row
+            .iter()
+            .zip(point.iter())
+            .all(|(&x, &y)| (x - y).abs() <= radius)
+        {
+            found.push(idx);
+        }
+
+        let axis = depth % dim;
+        let split = row[axis];
+
+        if point[axis] - radius <= split {
+            self.search(&nodes[..mid], point, radius, depth + 1, found);
+        }
+
+        if point[axis] + radius >= split {
+            self.search(&nodes[mid + 1..], point, radius, depth + 1, found);
+        }
+    }
+}
+
+// Coordinate-wise differences in which matching non-finite values cancel out and mismatched ones
+// put the rows infinitely far apart
+fn row_diffs<'b, F>(
+    x: &'b ArrayView1<F>,
+    y: &'b ArrayView1<F>,
+    nan_cmp: NanComparison,
+) -> impl Iterator<Item = F> + 'b
+where
+    F: Float + Display + Debug,
+{
+    x.iter().zip(y.iter()).map(move |(&a, &b)| {
+        if a == b || (a.is_nan() && b.is_nan() && nan_cmp == NanComparison::Equal) {
+            F::zero()
+        } else if a.is_finite() && b.is_finite() {
+            a - b
+        } else {
+            F::infinity()
+        }
+    })
+}
+
+// Widest gap along any axis between `row` and another row within `max_ulps` of it in every
+// coordinate. Adjacent values around `x` are at most `eps * |x|` apart, or `eps` times the
+// smallest normal value among subnormals, and the other row's coordinate may be the larger one.
+fn radius_ulps<F>(row: &ArrayView1<F>, max_ulps: u64) -> F
+where
+    F: Float + Display + Debug,
+{
+    if max_ulps == 0 {
+        return F::zero();
+    }
+
+    let ulps = F::from(max_ulps).unwrap_or(F::infinity()) * F::epsilon();
+
+    if ulps >= F::one() {
+        return F::infinity();
+    }
+
+    let mag_max = row.iter().fold(F::zero(), |acc, &x| acc.max(x.abs()));
+    ulps * (mag_max + F::min_positive_value()) / (F::one() - ulps)
+}
+
+// Greedy leader clustering: each row not yet claimed, taken in `order`, starts a group and
+// claims unclaimed rows as `strategy` allows, with `within_fn` deciding whether two rows are
+// within tolerance. Under `Anchor` the leader claims every row near it, under `Chaining` and
+// `Joint` each claimed row claims those near it in turn, and under `Complete` a row near the
+// leader is only claimed when near every member so far, taken in `order`. Rows with
+// non-finite values are kept out of the k-d tree and compared pairwise, since they can only
+// ever match one another
+pub(crate) fn cluster_rows<F, G>(
+    points: &ArrayView2<F>,
+    order: &[usize],
+    magnitudes: &[F],
+    tols: Tols<F>,
+    strategy: ClusterStrategy,
+    within_fn: G,
+) -> Vec<Vec<usize>>
+where
+    F: Float + Display + Debug,
+    G: Fn(usize, usize) -> bool,
+{
+    let n = points.nrows();
+    let is_finite: Vec<bool> = points
+        .axis_iter(Axis(0))
+        .map(|row| row.iter().all(|x| x.is_finite()))
+        .collect();
+
+    let (order_finite, order_nonfinite): (Vec<usize>, Vec<usize>) =
+        order.iter().partition(|&&idx| is_finite[idx]);
+    let tree = KdTree::new(points.view(), order_finite.clone());
+
+    let mut rank = vec![0; n];
+    for (k, &idx) in order.iter().enumerate() {
+        rank[idx] = k;
+    }
+
+    // Every row within tolerance of row `idx` is among those found here, along with others
+    let neighbours = |idx: usize, found: &mut Vec<usize>| {
+        found.clear();
+
+        if !is_finite[idx] {
+            found.extend_from_slice(&order_nonfinite);
+            return;
+        }
+
+        // By the triangle inequality the other row's magnitude is at most this one's plus their
+        // distance, which under any `ToleranceRule` caps the distance at this
+        let radius = match tols.rtol < F::one() {
+            true => (tols.atol + tols.rtol * magnitudes[idx]) / (F::one() - tols.rtol),
+            false => F::infinity(),
+        };
+        let radius = radius.max(radius_ulps(&points.row(idx), tols.max_ulps));
+        tree.within(&points.row(idx), radius, found);
+    };
+
+    let mut claimed = vec![false; n];
+    let mut groups = Vec::new();
+    let mut candidates = Vec::new();
+
+    for &idx in order_finite.iter().chain(order_nonfinite.iter()) {
+        if claimed[idx] {
+            continue;
+        }
+
+        claimed[idx] = true;
+        let mut group = vec![idx];
+
+        match strategy {
+            ClusterStrategy::Anchor => {
+                neighbours(idx, &mut candidates);
+
+                for &cand in candidates.iter() {
+                    if !claimed[cand] && within_fn(idx, cand) {
+                        claimed[cand] = true;
+                        group.push(cand);
+                    }
+                }
+            }
+            ClusterStrategy::Chaining | ClusterStrategy::Joint => {
+                let mut k = 0;
+
+                while k < group.len() {
+                    let member = group[k];
+                    neighbours(member, &mut candidates);
+
+                    for &cand in candidates.iter() {
+                        if !claimed[cand] && within_fn(member, cand) {
+                            claimed[cand] = true;
+                            group.push(cand);
+                        }
+                    }
+
+                    k += 1;
+                }
+            }
+            ClusterStrategy::Complete => {
+                neighbours(idx, &mut candidates);
+                candidates.sort_unstable_by_key(|&cand| rank[cand]);
+
+                for &cand in candidates.iter() {
+                    if !claimed[cand] && group.iter().all(|&member| within_fn(member, cand)) {
+                        claimed[cand] = true;
+                        group.push(cand);
+                    }
+                }
+            }
+        }
+
+        groups.push(group);
+    }
+
+    // Non-finite leaders are visited last but keep their place in `order`
+    groups.sort_unstable_by_key(|group| rank[group[0]]);
+    groups
+}
+
+// Lexicographic comparison with NaNs placed after every other value
+fn cmp_rows<F>(x: &ArrayView1<F>, y: &ArrayView1<F>) -> Ordering
+where
+    F: Float + Display + Debug,
+{
+    x.iter()
+        .zip(y.iter())
+        .map(|(a, b)| match (a.is_nan(), b.is_nan()) {
+            (false, false) => a.partial_cmp(b).unwrap(),
+            (is_nan_a, is_nan_b) => is_nan_a.cmp(&is_nan_b),
+        })
+        .find(|&ord| ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+pub fn uniquetol_points<F, S>(
+    points: &ArrayBase<S, Ix2>,
+    tols: Tols<F>,
+    nan_cmp: NanComparison,
+    occurrence: Occurrence,
+    norm: Norm<F>,
+    representative: Representative,
+    order: OutputOrder,
+) -> Result<UniqueTolNDResult<F, Ix2>, UniqueTolError<F>>
+where
+    F: Float + Display + Debug,
+    S: Data<Elem = F>,
+{
+    tols.validate()?;
+    norm.validate()?;
+
+    let arr = points.view().into_dyn();
+    check_flatten_axis(&arr, &FlattenAxis::Dim(0))?;
+
+    // Rows are visited in lexicographic order, so one-dimensional points cluster exactly as
+    // under `ClusterStrategy::Anchor`
+    let points = points.view();
+    let mut perm: Vec<usize> = (0..points.nrows()).collect();
+
+    match occurrence {
+        Occurrence::Lowest => perm.sort_by(|&i, &j| cmp_rows(&points.row(i), &points.row(j))),
+        Occurrence::Highest => perm.sort_by(|&i, &j| cmp_rows(&points.row(j), &points.row(i))),
+    }
+
+    let magnitudes: Vec<F> = points
+        .axis_iter(Axis(0))
+        .map(|row| {
+            norm.norm(
+                row.iter()
+                    .map(|&x| if x.is_finite() { x } else { F::zero() }),
+            )
+        })
+        .collect();
+
+    // Rows within `max_ulps` of each other in every coordinate match, just as the scalar values
+    // of a single column would under `isapprox`
+    let within_tol = |i: usize, j: usize| {
+        let (row_i, row_j) = (points.row(i), points.row(j));
+        let dist = norm.norm(row_diffs(&row_i, &row_j, nan_cmp));
+
+        dist <= tols.tol(magnitudes[i], magnitudes[j])
+            || (tols.max_ulps > 0
+                && row_i
+                    .iter()
+                    .zip(row_j.iter())
+                    .all(|(&a, &b)| a == b || isapprox_ulps(a, b, tols.max_ulps)))
+    };
+
+    let groups = cluster_rows(
+        &points,
+        &perm,
+        &magnitudes,
+        tols,
+        ClusterStrategy::Anchor,
+        within_tol,
+    );
+
+    // Every group is led by the row that claimed it
+    let result =
+        UniqueTolNDResult::from_groups(&arr, &groups, Occurrence::Lowest, FlattenAxis::Dim(0));
+
+    Ok(result
+        .finish(&arr, representative, order, Representative::summarize)
+        .into_dimensionality()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uniquetol_1d::{test_arr::TEST_ARR, uniquetol_1d};
+    use crate::uniquetol_nd::uniquetol_nd;
+    use ndarray::{Array2, array};
+
+    fn uniquetol_points_default(
+        points: &Array2<f64>,
+        tols: Tols<f64>,
+        norm: Norm<f64>,
+    ) -> UniqueTolNDResult<f64, Ix2> {
+        uniquetol_points(
+            points,
+            tols,
+            NanComparison::default(),
+            Occurrence::default(),
+            norm,
+            Representative::default(),
+            OutputOrder::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_uniquetol_points_norms() {
+        // The second point is 0.5 away along each axis from the first
+        let points = array![[0.0, 0.0], [0.5, 0.5], [3.0, 4.0], [3.4, 4.0]];
+        let tols = Tols::new(0.6, 0.0).unwrap();
+
+        let result = uniquetol_points_default(&points, tols, Norm::Chebyshev);
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 1]);
+
+        let result = uniquetol_points_default(&points, tols, Norm::Euclidean);
+        assert_eq!(result.inverse_unique, vec![0, 1, 2, 2]);
+        assert_eq!(result.indices_unique, vec![0, 1, 2]);
+        assert_eq!(result.counts_unique, vec![1, 1, 2]);
+        assert_eq!(result.arr_unique.shape(), [3, 2]);
+
+        let result = uniquetol_points_default(&points, tols, Norm::Minkowski(1.5));
+        assert_eq!(result.inverse_unique, vec![0, 1, 2, 2]);
+
+        let result =
+            uniquetol_points_default(&points, Tols::new(1.0, 0.0).unwrap(), Norm::Manhattan);
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 1]);
+
+        let result = uniquetol_points_default(&points, tols, Norm::Manhattan);
+        assert_eq!(result.inverse_unique, vec![0, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_uniquetol_points_ulps() {
+        let x = 1.0f64;
+        let y = f64::from_bits(x.to_bits() + 1);
+        let points = array![[x, -x], [y, -y], [x, 2.0]];
+        let tols = Tols::new(0.0, 0.0).unwrap().with_max_ulps(4);
+
+        let result = uniquetol_points_default(&points, tols, Norm::Euclidean);
+        assert_eq!(result.inverse_unique, vec![0, 0, 1]);
+
+        let result = uniquetol_nd(
+            &points,
+            tols,
+            NanComparison::default(),
+            Occurrence::default(),
+            ClusterStrategy::default(),
+            Representative::default(),
+            OutputOrder::default(),
+            FlattenAxis::Dim(0),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 1]);
+
+        let result =
+            uniquetol_points_default(&points, Tols::new(0.0, 0.0).unwrap(), Norm::Euclidean);
+        assert_eq!(result.get_len_unique(), 3);
+    }
+
+    #[test]
+    fn test_uniquetol_points_1d() {
+        let points = Array2::from_shape_vec((TEST_ARR.len(), 1), TEST_ARR.to_vec()).unwrap();
+        let tols = Tols::default();
+
+        for occurrence in [Occurrence::Lowest, Occurrence::Highest] {
+            let result = uniquetol_points(
+                &points,
+                tols,
+                NanComparison::default(),
+                occurrence,
+                Norm::Euclidean,
+                Representative::Member,
+                OutputOrder::Sorted,
+            )
+            .unwrap();
+            let result_1d = uniquetol_1d(
+                TEST_ARR,
+                tols,
+                NanComparison::default(),
+                occurrence,
+                ClusterStrategy::Anchor,
+                Representative::Member,
+                OutputOrder::Sorted,
+            );
+
+            assert_eq!(
+                result.arr_unique.into_raw_vec_and_offset().0,
+                result_1d.arr_unique
+            );
+            assert_eq!(result.indices_unique, result_1d.indices_unique);
+            assert_eq!(result.inverse_unique, result_1d.inverse_unique);
+            assert_eq!(result.counts_unique, result_1d.counts_unique);
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_points_cloud() {
+        // Jittered copies of a coarse 3-D grid, shuffled by a fixed stride
+        let n_grid = 5;
+        let n_copies = 4;
+        let n = n_grid * n_grid * n_grid * n_copies;
+        let mut points = Array2::zeros((n, 3));
+
+        for i in 0..n {
+            let idx = (i * 37) % n;
+            let cell = idx / n_copies;
+            let jitter = 1e-4 * (idx % n_copies) as f64;
+            points[[i, 0]] = (cell / (n_grid * n_grid)) as f64 + jitter;
+            points[[i, 1]] = (cell / n_grid % n_grid) as f64 - jitter;
+            points[[i, 2]] = (cell % n_grid) as f64 + jitter;
+        }
+
+        let result = uniquetol_points(
+            &points,
+            Tols::new(1e-3, 0.0).unwrap(),
+            NanComparison::default(),
+            Occurrence::default(),
+            Norm::Euclidean,
+            Representative::Mean,
+            OutputOrder::Stable,
+        )
+        .unwrap();
+
+        assert_eq!(result.get_len_unique(), n_grid * n_grid * n_grid);
+        assert!(result.counts_unique.iter().all(|&count| count == n_copies));
+        assert_eq!(result.inverse_unique[0], 0);
+
+        let remapped = result.remap_to_original();
+        assert!(
+            remapped
+                .iter()
+                .zip(points.iter())
+                .all(|(&x, &y)| (x - y).abs() < 1e-3)
+        );
+    }
+
+    #[test]
+    fn test_uniquetol_points_non_finite() {
+        let points = array![
+            [f64::NAN, 1.0],
+            [1.0, 1.0],
+            [f64::NAN, 1.0 + 1e-9],
+            [f64::INFINITY, 0.0],
+            [f64::INFINITY, 0.0],
+            [1.0, f64::NAN],
+        ];
+        let tols = Tols::new(1e-6, 0.0).unwrap();
+
+        let result = uniquetol_points_default(&points, tols, Norm::Euclidean);
+        assert_eq!(result.inverse_unique, vec![3, 0, 3, 2, 2, 1]);
+
+        let result = uniquetol_points(
+            &points,
+            tols,
+            NanComparison::NotEqual,
+            Occurrence::default(),
+            Norm::Euclidean,
+            Representative::default(),
+            OutputOrder::default(),
+        )
+        .unwrap();
+        assert_eq!(result.inverse_unique, vec![3, 0, 4, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_uniquetol_points_errors() {
+        let points = array![[0.0, 0.0], [1.0, 1.0]];
+        let result = uniquetol_points(
+            &points,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+            Norm::Minkowski(0.5),
+            Representative::default(),
+            OutputOrder::default(),
+        );
+        assert!(matches!(result, Err(UniqueTolError::NormOrder(_))));
+
+        let points = Array2::<f64>::zeros((0, 3));
+        let result = uniquetol_points(
+            &points,
+            Tols::default(),
+            NanComparison::default(),
+            Occurrence::default(),
+            Norm::default(),
+            Representative::default(),
+            OutputOrder::default(),
+        );
+        assert!(matches!(result, Err(UniqueTolError::EmptyAxis(0))));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_norm_serde() {
+        for norm in [Norm::Manhattan, Norm::Euclidean, Norm::Minkowski(1.5)] {
+            let json = serde_json::to_string(&norm).unwrap();
+            assert_eq!(serde_json::from_str::<Norm<f64>>(&json).unwrap(), norm);
+        }
+
+        let result = serde_json::from_str::<Norm<f64>>(r#"{"Minkowski": 0.5}"#);
+        assert!(result.is_err());
+    }
+}
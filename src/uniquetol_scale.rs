@@ -0,0 +1,381 @@
+// Copyright 2025 Luis M. B. Varona
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use alloc::vec;
+use core::fmt::{Debug, Display};
+use ndarray::{Array, ArrayView, Axis, IxDyn, arr0};
+use num_traits::Float;
+
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{FlattenAxis, check_flatten_axis, shape_slice};
+use crate::uniquetol_options::UniqueTolMetric;
+
+// The magnitude that `rtol` is taken relative to. MATLAB's `uniquetol` behaves like `Auto` with
+// `Tols::new(0.0, tol)`, comparing `|u - v| <= tol * max(abs(A(:)))`, or with `ByRows` set,
+// `tol * max(abs(A), [], 1)` one column at a time.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "DataScaleUnchecked<F>",
+        bound(deserialize = "F: Float + Display + Debug + serde::Deserialize<'de>")
+    )
+)]
+pub enum DataScale<F> {
+    // Relative to the values being compared, as in `isapprox`
+    #[default]
+    None,
+    // Relative to the largest finite magnitude in the whole input, or at each slice position
+    // when flattening along axes
+    Auto,
+    Value(F),
+    // One scale per slice position, shaped like a single slice along the flattened axes
+    Columns(Array<F, IxDyn>),
+}
+
+impl<F> DataScale<F>
+where
+    F: Float + Display + Debug,
+{
+    pub fn validate(&self) -> Result<(), UniqueTolError<F>> {
+        match self {
+            DataScale::None | DataScale::Auto => Ok(()),
+            DataScale::Value(scale) => check_scale(*scale),
+            DataScale::Columns(scales) => scales.iter().try_for_each(|&scale| check_scale(scale)),
+        }
+    }
+}
+
+// Deserialized scales are checked with `DataScale::validate`, as tolerances are by `Tols::new`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+enum DataScaleUnchecked<F> {
+    None,
+    Auto,
+    Value(F),
+    Columns(Array<F, IxDyn>),
+}
+
+#[cfg(feature = "serde")]
+impl<F> TryFrom<DataScaleUnchecked<F>> for DataScale<F>
+where
+    F: Float + Display + Debug,
+{
+    type Error = UniqueTolError<F>;
+
+    fn try_from(value: DataScaleUnchecked<F>) -> Result<Self, Self::Error> {
+        let data_scale = match value {
+            DataScaleUnchecked::None => DataScale::None,
+            DataScaleUnchecked::Auto => DataScale::Auto,
+            DataScaleUnchecked::Value(scale) => DataScale::Value(scale),
+            DataScaleUnchecked::Columns(scales) => DataScale::Columns(scales),
+        };
+        data_scale.validate().map(|_| data_scale)
+    }
+}
+
+fn check_scale<F>(scale: F) -> Result<(), UniqueTolError<F>>
+where
+    F: Float + Display + Debug,
+{
+    if scale.is_finite() && !scale.is_sign_negative() {
+        Ok(())
+    } else {
+        Err(UniqueTolError::DataScale(scale))
+    }
+}
+
+fn max_finite<F, I>(magnitudes: I) -> F
+where
+    F: Float + Display + Debug,
+    I: IntoIterator<Item = F>,
+{
+    magnitudes
+        .into_iter()
+        .filter(|x| x.is_finite())
+        .fold(F::zero(), F::max)
+}
+
+// Reduces away the flattened axes, leaving the largest finite magnitude at each slice position.
+// Without any, the whole input is a single position.
+fn max_finite_slices<E, F, G>(
+    arr: &ArrayView<E, IxDyn>,
+    flatten_axis: &FlattenAxis,
+    magnitude_fn: G,
+) -> Array<F, IxDyn>
+where
+    E: Copy,
+    F: Float + Display + Debug,
+    G: Fn(E) -> F,
+{
+    let mut axes = match flatten_axis {
+        FlattenAxis::None => {
+            return arr0(max_finite(arr.iter().map(|&x| magnitude_fn(x)))).into_dyn();
+        }
+        FlattenAxis::Dim(axis) => vec![*axis],
+        FlattenAxis::Dims(axes) => axes.clone(),
+    };
+
+    // Removing the highest axis first keeps the remaining axis indices valid
+    axes.sort_unstable_by(|a, b| b.cmp(a));
+    let mut scales = arr.map_axis(Axis(axes[0]), |lane| {
+        max_finite(lane.iter().map(|&x| magnitude_fn(x)))
+    });
+
+    for &axis in &axes[1..] {
+        scales = scales.map_axis(Axis(axis), |lane| max_finite(lane.iter().copied()));
+    }
+
+    scales
+}
+
+// The scale at each slice position, shaped like a single slice, or `None` if values are compared
+// relative to themselves. `magnitude_fn` gives the magnitude that `DataScale::Auto` looks at.
+pub(crate) fn slice_scales<E, F, G>(
+    arr: &ArrayView<E, IxDyn>,
+    data_scale: &DataScale<F>,
+    flatten_axis: &FlattenAxis,
+    magnitude_fn: G,
+) -> Result<Option<Array<F, IxDyn>>, UniqueTolError<F>>
+where
+    E: Copy,
+    F: Float + Display + Debug,
+    G: Fn(E) -> F,
+{
+    data_scale.validate()?;
+    check_flatten_axis(arr, flatten_axis)?;
+
+    let shape = shape_slice(arr.shape(), flatten_axis);
+
+    match data_scale {
+        DataScale::None => Ok(None),
+        DataScale::Auto => Ok(Some(max_finite_slices(arr, flatten_axis, magnitude_fn))),
+        DataScale::Value(scale) => Ok(Some(Array::from_elem(shape, *scale))),
+        DataScale::Columns(scales) if scales.shape() == shape.as_slice() => {
+            Ok(Some(scales.clone()))
+        }
+        DataScale::Columns(scales) => {
+            Err(UniqueTolError::TolsShape(shape, scales.shape().to_vec()))
+        }
+    }
+}
+
+// One metric per slice position, with `data_scale` applied to `tols`
+pub(crate) fn tols_per_position<E, F, T, G>(
+    arr: &ArrayView<E, IxDyn>,
+    tols: &T,
+    data_scale: &DataScale<F>,
+    flatten_axis: &FlattenAxis,
+    magnitude_fn: G,
+) -> Result<Array<T, IxDyn>, UniqueTolError<F>>
+where
+    E: Copy,
+    F: Float + Display + Debug,
+    T: UniqueTolMetric<F>,
+    G: Fn(E) -> F,
+{
+    tols.validate()?;
+
+    match slice_scales(arr, data_scale, flatten_axis, magnitude_fn)? {
+        Some(scales) => Ok(scales.mapv(|scale| tols.scaled(scale))),
+        None => Ok(Array::from_elem(
+            shape_slice(arr.shape(), flatten_axis),
+            tols.clone(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isapprox::Tols;
+    use crate::uniquetol_1d::OutputOrder;
+    use crate::uniquetol_options::UniqueTol;
+    use alloc::vec;
+    use ndarray::{arr0, array};
+
+    #[test]
+    fn test_uniquetol_1d_scaled() {
+        // MATLAB: uniquetol([0.05 0.11 0.18 0.19 0.82 1.0], 0.1) returns [0.05 0.18 0.82 1.0]
+        let arr = [0.05, 0.11, 0.18, 0.19, 0.82, 1.0, f64::INFINITY];
+        let tols = Tols::new(0.0, 0.1).unwrap();
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Auto)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(
+            result.arr_unique,
+            vec![0.05, 0.18, 0.82, 1.0, f64::INFINITY]
+        );
+        assert_eq!(result.counts_unique, vec![2, 2, 1, 1, 1]);
+
+        // Relative to each pair of values instead, only 0.18 and 0.19 lie close enough to merge
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::None)
+            .run(&arr[..6])
+            .unwrap();
+        assert_eq!(result.arr_unique, vec![0.05, 0.11, 0.18, 0.82, 1.0]);
+
+        // A purely relative tolerance is itself infinite against the infinity, which joins 1.0
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::None)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.arr_unique, vec![0.05, 0.11, 0.18, 0.82, 1.0]);
+        assert_eq!(result.counts_unique, vec![1, 1, 2, 1, 2]);
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Columns(arr0(10.0).into_dyn()))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.arr_unique, vec![0.05, f64::INFINITY]);
+
+        for data_scale in [
+            DataScale::Value(-1.0),
+            DataScale::Value(f64::NAN),
+            DataScale::Columns(array![1.0, 2.0].into_dyn()),
+        ] {
+            let result = UniqueTol::new().tols(tols).data_scale(data_scale).run(&arr);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_uniquetol_nd_scaled() {
+        let arr = array![[1.0, 300.0], [1.04, 300.0], [1.0, 330.0], [2.0, 300.0]];
+        let tols = Tols::new(0.0, 0.1).unwrap();
+
+        // Each column is scaled by its own largest magnitude, 2 and 330, as with MATLAB's `ByRows`
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Auto)
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dim(0))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 0, 1]);
+
+        // Scaled by 330 throughout, 2.0 lies within 33 of 1.0 and 330.0 within 33 of 300.0
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Auto)
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::None)
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.arr_unique, array![1.0, 300.0].into_dyn());
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Columns(array![1.0, 300.0].into_dyn()))
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dim(0))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 0, 1]);
+        assert_eq!(result.indices_unique, vec![0, 3]);
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Value(1.0))
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dim(0))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 0, 1, 2]);
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Columns(array![1.0, 300.0, 1.0].into_dyn()))
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dim(0))
+            .run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::TolsShape(_, _))));
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Columns(array![1.0, f64::INFINITY].into_dyn()))
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dim(0))
+            .run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::DataScale(_))));
+    }
+
+    #[test]
+    fn test_uniquetol_nd_scaled_auto() {
+        // The large column must not widen the tolerance of the small one
+        let arr = array![[1.0, 1000.0], [1.5, 1000.0]];
+        let tols = Tols::new(0.0, 1e-3).unwrap();
+
+        for flatten_axis in [FlattenAxis::Dim(0), FlattenAxis::Dims(vec![0])] {
+            let result = UniqueTol::new()
+                .tols(tols)
+                .data_scale(DataScale::Auto)
+                .flatten_axis(flatten_axis)
+                .run(&arr)
+                .unwrap();
+            assert_eq!(result.get_len_unique(), 2);
+        }
+
+        // Flattening axes 0 and 2 leaves one scale per position along axis 1, here 150 and 100
+        let arr = array![[[1.0, 100.0], [1.05, 100.0]], [[2.0, 150.0], [1.0, 100.0]]];
+        let scales = max_finite_slices(
+            &arr.view().into_dyn(),
+            &FlattenAxis::Dims(vec![2, 0]),
+            f64::abs,
+        );
+        assert_eq!(scales, array![150.0, 100.0].into_dyn());
+
+        let result = UniqueTol::new()
+            .tols(Tols::new(0.0, 0.1).unwrap())
+            .data_scale(DataScale::Auto)
+            .order(OutputOrder::Stable)
+            .flatten_axis(FlattenAxis::Dims(vec![0, 2]))
+            .run(&arr)
+            .unwrap();
+        assert_eq!(result.inverse_unique, vec![0, 1, 0, 2]);
+
+        let result = UniqueTol::new()
+            .tols(tols)
+            .data_scale(DataScale::Auto)
+            .flatten_axis(FlattenAxis::Dim(3))
+            .run(&arr);
+        assert!(matches!(result, Err(UniqueTolError::AxisBounds(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_data_scale_serde() {
+        for data_scale in [
+            DataScale::None,
+            DataScale::Auto,
+            DataScale::Value(2.5),
+            DataScale::Columns(array![[1.0, 2.0], [3.0, 4.0]].into_dyn()),
+        ] {
+            let json = serde_json::to_string(&data_scale).unwrap();
+            assert_eq!(
+                serde_json::from_str::<DataScale<f64>>(&json).unwrap(),
+                data_scale
+            );
+        }
+
+        for json in [
+            r#"{"Value": -1.0}"#,
+            r#"{"Columns": {"v": 1, "dim": [2], "data": [1.0, -2.0]}}"#,
+        ] {
+            assert!(serde_json::from_str::<DataScale<f64>>(json).is_err());
+        }
+        let result = serde_json::from_str::<DataScale<f64>>(r#"{"Value": 2.0}"#);
+        assert_eq!(result.unwrap(), DataScale::Value(2.0));
+    }
+}
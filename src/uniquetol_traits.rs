@@ -5,78 +5,220 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use ndarray::{Array, ArrayBase, Data, Dimension, IxDyn};
+use core::fmt::{Debug, Display};
+use ndarray::{ArrayBase, Data, Dimension, Ix1};
 use num_traits::Float;
-use std::fmt::{Debug, Display};
 
-use crate::isapprox::{NanComparison, Tols};
-use crate::uniquetol_1d::{Occurrence, UniqueTolResult, uniquetol_1d};
-use crate::uniquetol_nd::{FlattenAxis, uniquetol_nd};
+use crate::uniquetol_1d::UniqueTolResult;
+use crate::uniquetol_error::UniqueTolError;
+use crate::uniquetol_nd::{FlattenAxis, UniqueTolNDResult};
+use crate::uniquetol_options::{UniqueTolElement, UniqueTolMetric, UniqueTolOptions};
 
 const UNIQUETOL_ERR_MSG: &str = "Failed to compute unique values";
+const DIM_ERR_MSG: &str = "Failed to restore the static dimensionality of unique values";
 
-pub trait UniqueTol1D<F>
+// Method-call shorthands for `UniqueTolOptions::run`, taking options with any metric `T`. The
+// non-`try` methods panic on invalid options rather than returning the error.
+pub trait UniqueTol1D<F, E = F>
 where
     F: Float + Display + Debug,
 {
-    fn uniquetol(
+    fn try_uniquetol<T>(
         &self,
-        tols: Tols<F>,
-        nan_cmp: NanComparison,
-        occurrence: Occurrence,
-    ) -> UniqueTolResult<F>;
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<E>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>;
+
+    #[inline]
+    fn uniquetol<T>(&self, options: &UniqueTolOptions<F, T>) -> UniqueTolResult<E>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        self.try_uniquetol(options).expect(UNIQUETOL_ERR_MSG)
+    }
 }
 
-impl<A, F> UniqueTol1D<F> for A
+impl<A, E, F> UniqueTol1D<F, E> for A
 where
-    A: AsRef<[F]>,
+    A: AsRef<[E]>,
     F: Float + Display + Debug,
 {
     #[inline]
-    fn uniquetol(
+    fn try_uniquetol<T>(
         &self,
-        tols: Tols<F>,
-        nan_cmp: NanComparison,
-        occurrence: Occurrence,
-    ) -> UniqueTolResult<F> {
-        uniquetol_1d(self, tols, nan_cmp, occurrence)
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolResult<E>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        options.run(self.as_ref())
     }
 }
 
-pub trait UniqueTolND<F>
+pub trait UniqueTolND<F, E = F>
 where
     F: Float + Display + Debug,
 {
-    fn uniquetol(
+    type Dim: Dimension;
+
+    fn try_uniquetol<T>(
+        &self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<E>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>;
+
+    #[inline]
+    fn uniquetol<T>(&self, options: &UniqueTolOptions<F, T>) -> UniqueTolNDResult<E>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        self.try_uniquetol(options).expect(UNIQUETOL_ERR_MSG)
+    }
+
+    // Ignores `options.flatten_axis`, always flattening the whole input
+    fn try_uniquetol_flat<T>(
+        &self,
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<E, Ix1>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        let options = options.clone().flatten_axis(FlattenAxis::None);
+        self.try_uniquetol(&options)
+            .map(|result| result.into_dimensionality().expect(DIM_ERR_MSG))
+    }
+
+    #[inline]
+    fn uniquetol_flat<T>(&self, options: &UniqueTolOptions<F, T>) -> UniqueTolNDResult<E, Ix1>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        self.try_uniquetol_flat(options).expect(UNIQUETOL_ERR_MSG)
+    }
+
+    // Ignores `options.flatten_axis` in favour of `axis`
+    fn try_uniquetol_axis<T>(
+        &self,
+        options: &UniqueTolOptions<F, T>,
+        axis: usize,
+    ) -> Result<UniqueTolNDResult<E, Self::Dim>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        let options = options.clone().axis(axis);
+        self.try_uniquetol(&options)
+            .map(|result| result.into_dimensionality().expect(DIM_ERR_MSG))
+    }
+
+    #[inline]
+    fn uniquetol_axis<T>(
         &self,
-        tols: Tols<F>,
-        nan_cmp: NanComparison,
-        occurrence: Occurrence,
-        flatten_axis: FlattenAxis,
-    ) -> Array<F, IxDyn>;
+        options: &UniqueTolOptions<F, T>,
+        axis: usize,
+    ) -> UniqueTolNDResult<E, Self::Dim>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        self.try_uniquetol_axis(options, axis)
+            .expect(UNIQUETOL_ERR_MSG)
+    }
 }
 
-impl<T, D, F> UniqueTolND<F> for &ArrayBase<T, D>
+impl<S, D, E, F> UniqueTolND<F, E> for &ArrayBase<S, D>
 where
-    T: Data<Elem = F>,
-    F: Float + Display + Debug,
+    S: Data<Elem = E>,
     D: Dimension,
+    F: Float + Display + Debug,
 {
+    type Dim = D;
+
     #[inline]
-    fn uniquetol(
+    fn try_uniquetol<T>(
         &self,
-        tols: Tols<F>,
-        nan_cmp: NanComparison,
-        occurrence: Occurrence,
-        flatten_axis: FlattenAxis,
-    ) -> Array<F, IxDyn> {
-        uniquetol_nd(
-            &self.mapv(|x| x).into_dyn(),
-            tols,
-            nan_cmp,
-            occurrence,
-            flatten_axis,
-        )
-        .expect(UNIQUETOL_ERR_MSG)
+        options: &UniqueTolOptions<F, T>,
+    ) -> Result<UniqueTolNDResult<E>, UniqueTolError<F>>
+    where
+        E: UniqueTolElement<F, T>,
+        T: UniqueTolMetric<F>,
+    {
+        options.run(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isapprox::{ToleranceRule, Tols, TolsError};
+    use crate::uniquetol_options::UniqueTol;
+    use alloc::vec;
+    use ndarray::{Array1, Array2, Ix2, array};
+
+    #[test]
+    fn test_try_uniquetol() {
+        let tols_bad = Tols {
+            atol: -1e-8,
+            rtol: 0.0,
+            max_ulps: 0,
+            rule: ToleranceRule::default(),
+        };
+        let options_bad = UniqueTol::new().tols(tols_bad);
+
+        let arr = [1.0, 2.0, 1.0];
+        let result = arr.try_uniquetol(&options_bad);
+        assert!(matches!(
+            result,
+            Err(UniqueTolError::Tols(TolsError::NegativeAtol(_)))
+        ));
+
+        let result = arr.try_uniquetol(&UniqueTol::new()).unwrap();
+        assert_eq!(result.arr_unique, vec![1.0, 2.0]);
+        assert_eq!(arr.uniquetol(&UniqueTol::new()), result);
+
+        let arr = Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 1.0, 2.0]).unwrap();
+        let result = (&arr).try_uniquetol(&UniqueTol::new().axis(3));
+        assert!(matches!(result, Err(UniqueTolError::AxisBounds(_))));
+
+        let result = (&arr).try_uniquetol(&UniqueTol::new().axis(0)).unwrap();
+        assert_eq!(result.counts_unique, vec![2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to compute unique values")]
+    fn test_uniquetol_invalid() {
+        let options = UniqueTol::new().atol(-1.0);
+        [1.0, 2.0].uniquetol(&options);
+    }
+
+    #[test]
+    fn test_uniquetol_static_dim() {
+        // The axis and flat methods override whichever axis the options name
+        let options = UniqueTol::new().axis(5);
+
+        let arr = Array2::from_shape_vec((3, 2), vec![1.0, 2.0, 3.0, 4.0, 1.0, 2.0]).unwrap();
+        let result: UniqueTolNDResult<f64, Ix2> = (&arr).uniquetol_axis(&options, 0);
+        let arr_remapped: Array2<f64> = result.remap_to_original();
+        assert_eq!(arr_remapped, arr);
+        let arr_unique: Array2<f64> = result.arr_unique;
+        assert_eq!(arr_unique, array![[1.0, 2.0], [3.0, 4.0]]);
+
+        let result: UniqueTolNDResult<f64, Ix1> = (&arr).uniquetol_flat(&options);
+        let arr_remapped: Array1<f64> = result.remap_to_original();
+        assert_eq!(arr_remapped, Array1::from_iter(arr.iter().copied()));
+        let arr_unique: Array1<f64> = result.arr_unique;
+        assert_eq!(arr_unique, array![1.0, 2.0, 3.0, 4.0]);
+
+        let result = (&arr.view()).try_uniquetol_axis(&options, 2);
+        assert!(matches!(result, Err(UniqueTolError::AxisBounds(_))));
     }
 }